@@ -0,0 +1,329 @@
+use std::fmt;
+
+use crate::game_state::{basic_play, is_batter_event};
+
+// Every symbol that can appear in a Retrosheet pitch sequence, see
+// https://www.retrosheet.org/eventfile.htm#5
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum PitchCode {
+    AutomaticStrike,     // A
+    Ball,                // B
+    CalledStrike,        // C
+    Foul,                // F
+    HitBatter,           // H
+    IntentionalBall,     // I
+    StrikeUnknownType,   // K
+    FoulBunt,            // L
+    MissedBunt,          // M
+    NoPitch,             // N, used on balks and interference calls
+    FoulTipOnBunt,       // O
+    Pitchout,            // P
+    SwingingOnPitchout,  // Q
+    FoulOnPitchout,      // R
+    SwingingStrike,      // S
+    FoulTip,             // T
+    Unknown,             // U, unknown or missed pitch
+    AutomaticBall,       // V, called ball because the pitcher went to his mouth
+    InPlay,              // X
+    InPlayOnPitchout,    // Y
+    CatcherPickoffThrow, // +, following pickoff throw by the catcher
+    BlockedByCatcher,    // *, following pitch was blocked by the catcher
+    NonBatterPlay,       // ., marker for a play not involving the batter
+    PickoffThrow(u8),    // 1, 2, 3, pickoff throw to that base
+    RunnerGoing,         // >, runner going on the pitch
+}
+
+impl PitchCode {
+    pub fn from_char(code: char) -> Option<PitchCode> {
+        let pitch = match code {
+            'A' => PitchCode::AutomaticStrike,
+            'B' => PitchCode::Ball,
+            'C' => PitchCode::CalledStrike,
+            'F' => PitchCode::Foul,
+            'H' => PitchCode::HitBatter,
+            'I' => PitchCode::IntentionalBall,
+            'K' => PitchCode::StrikeUnknownType,
+            'L' => PitchCode::FoulBunt,
+            'M' => PitchCode::MissedBunt,
+            'N' => PitchCode::NoPitch,
+            'O' => PitchCode::FoulTipOnBunt,
+            'P' => PitchCode::Pitchout,
+            'Q' => PitchCode::SwingingOnPitchout,
+            'R' => PitchCode::FoulOnPitchout,
+            'S' => PitchCode::SwingingStrike,
+            'T' => PitchCode::FoulTip,
+            'U' => PitchCode::Unknown,
+            'V' => PitchCode::AutomaticBall,
+            'X' => PitchCode::InPlay,
+            'Y' => PitchCode::InPlayOnPitchout,
+            '+' => PitchCode::CatcherPickoffThrow,
+            '*' => PitchCode::BlockedByCatcher,
+            '.' => PitchCode::NonBatterPlay,
+            '1' => PitchCode::PickoffThrow(1),
+            '2' => PitchCode::PickoffThrow(2),
+            '3' => PitchCode::PickoffThrow(3),
+            '>' => PitchCode::RunnerGoing,
+            _ => return None,
+        };
+        Some(pitch)
+    }
+
+    pub fn to_char(&self) -> char {
+        match self {
+            PitchCode::AutomaticStrike => 'A',
+            PitchCode::Ball => 'B',
+            PitchCode::CalledStrike => 'C',
+            PitchCode::Foul => 'F',
+            PitchCode::HitBatter => 'H',
+            PitchCode::IntentionalBall => 'I',
+            PitchCode::StrikeUnknownType => 'K',
+            PitchCode::FoulBunt => 'L',
+            PitchCode::MissedBunt => 'M',
+            PitchCode::NoPitch => 'N',
+            PitchCode::FoulTipOnBunt => 'O',
+            PitchCode::Pitchout => 'P',
+            PitchCode::SwingingOnPitchout => 'Q',
+            PitchCode::FoulOnPitchout => 'R',
+            PitchCode::SwingingStrike => 'S',
+            PitchCode::FoulTip => 'T',
+            PitchCode::Unknown => 'U',
+            PitchCode::AutomaticBall => 'V',
+            PitchCode::InPlay => 'X',
+            PitchCode::InPlayOnPitchout => 'Y',
+            PitchCode::CatcherPickoffThrow => '+',
+            PitchCode::BlockedByCatcher => '*',
+            PitchCode::NonBatterPlay => '.',
+            PitchCode::PickoffThrow(base) => char::from(b'0' + base),
+            PitchCode::RunnerGoing => '>',
+        }
+    }
+
+    // Pitches actually delivered to the batter, as opposed to throws and markers
+    pub fn is_pitch(&self) -> bool {
+        match self {
+            PitchCode::NoPitch
+            | PitchCode::CatcherPickoffThrow
+            | PitchCode::BlockedByCatcher
+            | PitchCode::NonBatterPlay
+            | PitchCode::PickoffThrow(_)
+            | PitchCode::RunnerGoing => false,
+            PitchCode::AutomaticStrike
+            | PitchCode::Ball
+            | PitchCode::CalledStrike
+            | PitchCode::Foul
+            | PitchCode::HitBatter
+            | PitchCode::IntentionalBall
+            | PitchCode::StrikeUnknownType
+            | PitchCode::FoulBunt
+            | PitchCode::MissedBunt
+            | PitchCode::FoulTipOnBunt
+            | PitchCode::Pitchout
+            | PitchCode::SwingingOnPitchout
+            | PitchCode::FoulOnPitchout
+            | PitchCode::SwingingStrike
+            | PitchCode::FoulTip
+            | PitchCode::Unknown
+            | PitchCode::AutomaticBall
+            | PitchCode::InPlay
+            | PitchCode::InPlayOnPitchout => true,
+        }
+    }
+
+    // Pitches called a ball without the batter offering at them
+    pub fn is_ball(&self) -> bool {
+        matches!(
+            self,
+            PitchCode::Ball
+                | PitchCode::IntentionalBall
+                | PitchCode::Pitchout
+                | PitchCode::AutomaticBall
+        )
+    }
+
+    // Strikes the batter took
+    pub fn is_called_strike(&self) -> bool {
+        matches!(self, PitchCode::AutomaticStrike | PitchCode::CalledStrike)
+    }
+
+    // Any pitch the batter offered at: misses, fouls, bunts and balls in play
+    pub fn is_swing(&self) -> bool {
+        matches!(
+            self,
+            PitchCode::Foul
+                | PitchCode::FoulBunt
+                | PitchCode::MissedBunt
+                | PitchCode::FoulTipOnBunt
+                | PitchCode::SwingingOnPitchout
+                | PitchCode::FoulOnPitchout
+                | PitchCode::SwingingStrike
+                | PitchCode::FoulTip
+                | PitchCode::InPlay
+                | PitchCode::InPlayOnPitchout
+        )
+    }
+
+    // Fouls only count as a strike with fewer than two strikes, unlike foul bunts and foul tips
+    pub fn is_foul(&self) -> bool {
+        matches!(self, PitchCode::Foul | PitchCode::FoulOnPitchout)
+    }
+
+    pub fn is_in_play(&self) -> bool {
+        matches!(self, PitchCode::InPlay | PitchCode::InPlayOnPitchout)
+    }
+
+    // Pitchouts are thrown well outside the zone on purpose, so they are balls when not swung at
+    pub fn is_pitchout(&self) -> bool {
+        matches!(
+            self,
+            PitchCode::Pitchout
+                | PitchCode::SwingingOnPitchout
+                | PitchCode::FoulOnPitchout
+                | PitchCode::InPlayOnPitchout
+        )
+    }
+}
+
+impl fmt::Display for PitchCode {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.to_char())
+    }
+}
+
+// Move the count along for a pitch. Returns true once the plate appearance is over: ball four,
+// strike three, a hit batter or a ball in play.
+pub(crate) fn apply_pitch(pitch: &PitchCode, balls: &mut i32, strikes: &mut i32) -> bool {
+    if pitch == &PitchCode::HitBatter || pitch.is_in_play() {
+        return true;
+    }
+    if pitch.is_ball() {
+        *balls += 1;
+    } else if !pitch.is_foul() || *strikes < 2 {
+        *strikes += 1;
+    }
+    *balls >= 4 || *strikes >= 3
+}
+
+// Convert a raw pitch sequence like `CB1BX`, skipping any character Retrosheet does not define
+pub fn parse_pitch_codes(sequence: &str) -> Vec<PitchCode> {
+    sequence.chars().filter_map(PitchCode::from_char).collect()
+}
+
+// How a plate appearance ended, see https://www.retrosheet.org/eventfile.htm#5
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum PlateOutcome {
+    Single,
+    Double,
+    Triple,
+    HomeRun,
+    Walk,
+    IntentionalWalk,
+    HitByPitch,
+    Strikeout,
+    FieldedOut,
+    Error,
+    FieldersChoice,
+    SacrificeFly,
+    SacrificeBunt,
+    Interference,
+}
+
+impl PlateOutcome {
+    // Classify a Retrosheet event string. Returns None for plays that do not end the plate
+    // appearance (stolen bases, wild pitches, pickoffs, foul fly errors, ...).
+    pub fn from_event(event: &str) -> Option<PlateOutcome> {
+        let basic = basic_play(event);
+        let primary = basic.split('+').next().unwrap_or("");
+        let modifiers = event
+            .split('.')
+            .next()
+            .unwrap_or("")
+            .split('/')
+            .skip(1)
+            .collect::<Vec<&str>>();
+        let sacrifice = if modifiers.contains(&"SF") {
+            Some(PlateOutcome::SacrificeFly)
+        } else if modifiers.contains(&"SH") {
+            Some(PlateOutcome::SacrificeBunt)
+        } else {
+            None
+        };
+
+        if !is_batter_event(event) {
+            return None;
+        }
+        let outcome = if primary.starts_with("HP") {
+            PlateOutcome::HitByPitch
+        } else if primary.starts_with('I') {
+            // IW, or I in older files
+            PlateOutcome::IntentionalWalk
+        } else if primary.starts_with('W') {
+            PlateOutcome::Walk
+        } else if primary.starts_with('K') {
+            PlateOutcome::Strikeout
+        } else if primary.starts_with('C') {
+            PlateOutcome::Interference
+        } else if primary.starts_with('S') {
+            PlateOutcome::Single
+        } else if primary.starts_with('D') {
+            PlateOutcome::Double
+        } else if primary.starts_with('T') {
+            PlateOutcome::Triple
+        } else if primary.starts_with('H') {
+            PlateOutcome::HomeRun
+        } else if primary.starts_with("FC") {
+            sacrifice.unwrap_or(PlateOutcome::FieldersChoice)
+        } else if primary.starts_with('E') {
+            sacrifice.unwrap_or(PlateOutcome::Error)
+        } else if primary.starts_with(|c: char| c.is_ascii_digit()) {
+            if primary.contains('E') {
+                sacrifice.unwrap_or(PlateOutcome::Error)
+            } else {
+                sacrifice.unwrap_or(PlateOutcome::FieldedOut)
+            }
+        } else {
+            return None;
+        };
+        Some(outcome)
+    }
+
+    pub fn is_hit(&self) -> bool {
+        matches!(
+            self,
+            PlateOutcome::Single
+                | PlateOutcome::Double
+                | PlateOutcome::Triple
+                | PlateOutcome::HomeRun
+        )
+    }
+
+    // Walks of either kind, hit batters are counted separately
+    pub fn is_walk(&self) -> bool {
+        matches!(self, PlateOutcome::Walk | PlateOutcome::IntentionalWalk)
+    }
+
+    // Short code used when printing plate appearances
+    pub fn to_code(&self) -> &'static str {
+        match self {
+            PlateOutcome::Single => "S",
+            PlateOutcome::Double => "D",
+            PlateOutcome::Triple => "T",
+            PlateOutcome::HomeRun => "HR",
+            PlateOutcome::Walk => "W",
+            PlateOutcome::IntentionalWalk => "IW",
+            PlateOutcome::HitByPitch => "HP",
+            PlateOutcome::Strikeout => "K",
+            PlateOutcome::FieldedOut => "O",
+            PlateOutcome::Error => "E",
+            PlateOutcome::FieldersChoice => "FC",
+            PlateOutcome::SacrificeFly => "SF",
+            PlateOutcome::SacrificeBunt => "SH",
+            PlateOutcome::Interference => "C",
+        }
+    }
+}
+
+impl fmt::Display for PlateOutcome {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.to_code())
+    }
+}
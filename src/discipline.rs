@@ -0,0 +1,627 @@
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::error::{Error, Result};
+use crate::registry::normalize_name;
+use crate::roster::Hand;
+use crate::season_directory;
+
+// Columns of the FanGraphs plate discipline table, see
+// https://library.fangraphs.com/offense/plate-discipline/
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum DisciplineColumn {
+    Name,
+    Team,
+    PlayerId,
+    OSwing,
+    ZSwing,
+    Swing,
+    OContact,
+    ZContact,
+    Contact,
+    Zone,
+    FStrike,
+    SwStr,
+    CStr,
+    Csw,
+}
+
+impl DisciplineColumn {
+    // Match a header cell, ignoring case, spaces and punctuation so `O-Swing%`, `O-Swing %`
+    // and `oswing` are the same column
+    pub fn from_header(header: &str) -> Option<DisciplineColumn> {
+        let column = match normalize_header(header).as_str() {
+            "name" | "player" | "playername" => DisciplineColumn::Name,
+            "team" | "tm" => DisciplineColumn::Team,
+            "playerid" | "fangraphsid" | "idfg" => DisciplineColumn::PlayerId,
+            "oswing" => DisciplineColumn::OSwing,
+            "zswing" => DisciplineColumn::ZSwing,
+            "swing" => DisciplineColumn::Swing,
+            "ocontact" => DisciplineColumn::OContact,
+            "zcontact" => DisciplineColumn::ZContact,
+            "contact" => DisciplineColumn::Contact,
+            "zone" => DisciplineColumn::Zone,
+            "fstrike" => DisciplineColumn::FStrike,
+            "swstr" => DisciplineColumn::SwStr,
+            "cstr" => DisciplineColumn::CStr,
+            "csw" => DisciplineColumn::Csw,
+            _ => return None,
+        };
+        Some(column)
+    }
+
+    // Column order of the table copied straight off the FanGraphs leaderboard page, which has
+    // no header row
+    fn leaderboard_layout() -> Vec<Option<DisciplineColumn>> {
+        vec![
+            None, // row number
+            Some(DisciplineColumn::Name),
+            Some(DisciplineColumn::Team),
+            Some(DisciplineColumn::OSwing),
+            Some(DisciplineColumn::ZSwing),
+            Some(DisciplineColumn::Swing),
+            Some(DisciplineColumn::OContact),
+            Some(DisciplineColumn::ZContact),
+            Some(DisciplineColumn::Contact),
+            Some(DisciplineColumn::Zone),
+            Some(DisciplineColumn::FStrike),
+            Some(DisciplineColumn::SwStr),
+            Some(DisciplineColumn::CStr),
+            Some(DisciplineColumn::Csw),
+        ]
+    }
+}
+
+// One player's row of the plate discipline table. Rates are percentages (29.6, not 0.296).
+// O-Swing%, Swing% and Zone% drive the simulation and are always present.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PlateDiscipline {
+    name: String,
+    team: Option<String>,
+    fangraphs_id: Option<String>,
+    oswing_pct: f32,
+    zswing_pct: Option<f32>,
+    swing_pct: f32,
+    ocontact_pct: Option<f32>,
+    zcontact_pct: Option<f32>,
+    contact_pct: Option<f32>,
+    zone_pct: f32,
+    fstrike_pct: Option<f32>,
+    swstr_pct: Option<f32>,
+    cstr_pct: Option<f32>,
+    csw_pct: Option<f32>,
+}
+
+impl PlateDiscipline {
+    // A row with only the three rates the simulation needs
+    pub fn new(name: String, oswing_pct: f32, swing_pct: f32, zone_pct: f32) -> Self {
+        Self {
+            name,
+            team: None,
+            fangraphs_id: None,
+            oswing_pct,
+            zswing_pct: None,
+            swing_pct,
+            ocontact_pct: None,
+            zcontact_pct: None,
+            contact_pct: None,
+            zone_pct,
+            fstrike_pct: None,
+            swstr_pct: None,
+            cstr_pct: None,
+            csw_pct: None,
+        }
+    }
+    pub fn get_name(&self) -> &String {
+        &self.name
+    }
+    pub fn get_team(&self) -> &Option<String> {
+        &self.team
+    }
+    pub fn get_fangraphs_id(&self) -> &Option<String> {
+        &self.fangraphs_id
+    }
+    pub fn get_oswing_pct(&self) -> &f32 {
+        &self.oswing_pct
+    }
+    pub fn get_zswing_pct(&self) -> &Option<f32> {
+        &self.zswing_pct
+    }
+    pub fn get_swing_pct(&self) -> &f32 {
+        &self.swing_pct
+    }
+    pub fn get_ocontact_pct(&self) -> &Option<f32> {
+        &self.ocontact_pct
+    }
+    pub fn get_zcontact_pct(&self) -> &Option<f32> {
+        &self.zcontact_pct
+    }
+    pub fn get_contact_pct(&self) -> &Option<f32> {
+        &self.contact_pct
+    }
+    pub fn get_zone_pct(&self) -> &f32 {
+        &self.zone_pct
+    }
+    pub fn get_fstrike_pct(&self) -> &Option<f32> {
+        &self.fstrike_pct
+    }
+    pub fn get_swstr_pct(&self) -> &Option<f32> {
+        &self.swstr_pct
+    }
+    pub fn get_cstr_pct(&self) -> &Option<f32> {
+        &self.cstr_pct
+    }
+    pub fn get_csw_pct(&self) -> &Option<f32> {
+        &self.csw_pct
+    }
+    // The (O-Swing%, Swing%, Zone%) triple the simulator takes
+    pub fn simulation_rates(&self) -> (f32, f32, f32) {
+        (self.oswing_pct, self.swing_pct, self.zone_pct)
+    }
+}
+
+// Header cell lowercased with everything but letters and digits dropped, so `O-Swing %` reads as
+// `oswing` and `wOBAScale` as `wobascale`
+pub(crate) fn normalize_header(header: &str) -> String {
+    header
+        .chars()
+        .filter(|c| c.is_alphanumeric())
+        .collect::<String>()
+        .to_lowercase()
+}
+
+// Split a delimited line, honouring double-quoted fields with `""` escapes
+pub(crate) fn split_fields(line: &str, delimiter: char) -> Vec<String> {
+    let mut fields: Vec<String> = Vec::new();
+    let mut field = String::new();
+    let mut in_quotes = false;
+    let mut chars = line.chars().peekable();
+    while let Some(c) = chars.next() {
+        if in_quotes {
+            if c == '"' {
+                if chars.peek() == Some(&'"') {
+                    field.push('"');
+                    chars.next();
+                } else {
+                    in_quotes = false;
+                }
+            } else {
+                field.push(c);
+            }
+        } else if c == '"' {
+            in_quotes = true;
+        } else if c == delimiter {
+            fields.push(field.trim().to_owned());
+            field.clear();
+        } else {
+            field.push(c);
+        }
+    }
+    fields.push(field.trim().to_owned());
+    fields
+}
+
+fn parse_rate(value: &str, scale: f32, file: &str, line: usize) -> Result<f32> {
+    let value = value.trim();
+    let has_percent = value.ends_with('%');
+    let rate = value
+        .trim_end_matches('%')
+        .trim()
+        .parse::<f32>()
+        .map_err(|_| Error::malformed(file, line, format!("bad percentage {:?}", value)))?;
+    if has_percent {
+        Ok(rate)
+    } else {
+        Ok(rate * scale)
+    }
+}
+
+// A season's FanGraphs plate discipline table, indexed by normalized player name and FanGraphs
+// ID. Rows are kept as text and only parsed when looked up so one bad row does not stop the rest
+// of the table loading.
+pub struct DisciplineTable {
+    file: String,
+    year: i32,
+    columns: HashMap<DisciplineColumn, usize>,
+    // FanGraphs CSV exports write rates as fractions (0.296) rather than percentages
+    scale: f32,
+    // Line number and fields of every row
+    rows: Vec<(usize, Vec<String>)>,
+    names: HashMap<String, Vec<usize>>,
+    fangraphs_ids: HashMap<String, usize>,
+}
+
+impl DisciplineTable {
+    // Accepts tab or comma separated tables, with or without a header row. Without a header the
+    // columns are assumed to be in the order of the FanGraphs leaderboard page.
+    pub fn parse(contents: &str, file: &str, year: i32) -> Result<Self> {
+        let mut lines = contents
+            .lines()
+            .enumerate()
+            .map(|(index, line)| (index + 1, line.trim_start_matches('\u{feff}').trim_end()))
+            .filter(|(_, line)| !line.trim().is_empty())
+            .peekable();
+        let first_line = match lines.peek() {
+            Some((_, line)) => *line,
+            None => {
+                return Ok(Self {
+                    file: file.to_owned(),
+                    year,
+                    columns: HashMap::new(),
+                    scale: 1.0,
+                    rows: Vec::new(),
+                    names: HashMap::new(),
+                    fangraphs_ids: HashMap::new(),
+                })
+            }
+        };
+        let delimiter = if first_line.contains('\t') { '\t' } else { ',' };
+        let first_fields = split_fields(first_line, delimiter);
+        let header = first_fields
+            .iter()
+            .map(|field| DisciplineColumn::from_header(field))
+            .collect::<Vec<Option<DisciplineColumn>>>();
+        // Any cell naming a column makes the first line a header, so a header missing one of
+        // the required columns is an error rather than a row read in the leaderboard order
+        let layout = if header.iter().any(Option::is_some) {
+            lines.next();
+            header
+        } else {
+            DisciplineColumn::leaderboard_layout()
+        };
+        let columns = layout
+            .iter()
+            .enumerate()
+            .filter_map(|(index, column)| column.map(|column| (column, index)))
+            .collect::<HashMap<DisciplineColumn, usize>>();
+        for required in [
+            DisciplineColumn::Name,
+            DisciplineColumn::OSwing,
+            DisciplineColumn::Swing,
+            DisciplineColumn::Zone,
+        ] {
+            if !columns.contains_key(&required) {
+                return Err(Error::malformed(
+                    file,
+                    1,
+                    format!("plate discipline table has no {:?} column", required),
+                ));
+            }
+        }
+
+        let name_column = columns[&DisciplineColumn::Name];
+        let id_column = columns.get(&DisciplineColumn::PlayerId).copied();
+        let mut rows: Vec<(usize, Vec<String>)> = Vec::new();
+        let mut names: HashMap<String, Vec<usize>> = HashMap::new();
+        let mut fangraphs_ids: HashMap<String, usize> = HashMap::new();
+        let mut any_percent_sign = false;
+        let mut largest_rate: f32 = 0.0;
+        for (line, text) in lines {
+            let fields = split_fields(text, delimiter);
+            if fields.len() <= name_column || fields[name_column].is_empty() {
+                continue;
+            }
+            for (column, index) in &columns {
+                if matches!(
+                    column,
+                    DisciplineColumn::Name | DisciplineColumn::Team | DisciplineColumn::PlayerId
+                ) {
+                    continue;
+                }
+                if let Some(value) = fields.get(*index) {
+                    any_percent_sign |= value.ends_with('%');
+                    if let Ok(rate) = value.trim_end_matches('%').trim().parse::<f32>() {
+                        largest_rate = largest_rate.max(rate);
+                    }
+                }
+            }
+            names
+                .entry(normalize_name(&fields[name_column]))
+                .or_default()
+                .push(rows.len());
+            if let Some(id) = id_column.and_then(|index| fields.get(index)) {
+                if !id.is_empty() {
+                    fangraphs_ids.insert(id.clone(), rows.len());
+                }
+            }
+            rows.push((line, fields));
+        }
+        let scale = if !any_percent_sign && largest_rate > 0.0 && largest_rate <= 1.0 {
+            100.0
+        } else {
+            1.0
+        };
+        Ok(Self {
+            file: file.to_owned(),
+            year,
+            columns,
+            scale,
+            rows,
+            names,
+            fangraphs_ids,
+        })
+    }
+
+    pub fn get_year(&self) -> &i32 {
+        &self.year
+    }
+
+    // Names of every player in the table, as written in it
+    pub fn player_names(&self) -> Vec<&String> {
+        let name_column = self.columns[&DisciplineColumn::Name];
+        self.rows
+            .iter()
+            .map(|(_, fields)| &fields[name_column])
+            .collect()
+    }
+
+    // Highest Zone% any hitter in the table saw, about as many strikes as the league's pitchers
+    // can throw on purpose
+    pub fn max_zone_pct(&self) -> Option<f32> {
+        let zone_column = self.columns[&DisciplineColumn::Zone];
+        self.rows
+            .iter()
+            .filter_map(|(line, fields)| {
+                parse_rate(fields.get(zone_column)?, self.scale, &self.file, *line).ok()
+            })
+            .max_by(|a, b| a.total_cmp(b))
+    }
+
+    // Average Zone% over every row of the table
+    pub fn mean_zone_pct(&self) -> Option<f32> {
+        let zone_column = self.columns[&DisciplineColumn::Zone];
+        let rates = self
+            .rows
+            .iter()
+            .filter_map(|(line, fields)| {
+                parse_rate(fields.get(zone_column)?, self.scale, &self.file, *line).ok()
+            })
+            .collect::<Vec<f32>>();
+        if rates.is_empty() {
+            return None;
+        }
+        Some(rates.iter().sum::<f32>() / rates.len() as f32)
+    }
+
+    fn parse_row(&self, line: usize, fields: &[String]) -> Result<PlateDiscipline> {
+        let text = |column: DisciplineColumn| {
+            self.columns
+                .get(&column)
+                .and_then(|index| fields.get(*index))
+                .filter(|value| !value.is_empty())
+                .cloned()
+        };
+        let optional_rate = |column: DisciplineColumn| -> Result<Option<f32>> {
+            match text(column) {
+                Some(value) => parse_rate(&value, self.scale, &self.file, line).map(Some),
+                None => Ok(None),
+            }
+        };
+        let rate = |column: DisciplineColumn| -> Result<f32> {
+            optional_rate(column)?.ok_or_else(|| {
+                Error::malformed(&self.file, line, format!("missing {:?} value", column))
+            })
+        };
+        Ok(PlateDiscipline {
+            name: text(DisciplineColumn::Name).unwrap_or_default(),
+            team: text(DisciplineColumn::Team),
+            fangraphs_id: text(DisciplineColumn::PlayerId),
+            oswing_pct: rate(DisciplineColumn::OSwing)?,
+            zswing_pct: optional_rate(DisciplineColumn::ZSwing)?,
+            swing_pct: rate(DisciplineColumn::Swing)?,
+            ocontact_pct: optional_rate(DisciplineColumn::OContact)?,
+            zcontact_pct: optional_rate(DisciplineColumn::ZContact)?,
+            contact_pct: optional_rate(DisciplineColumn::Contact)?,
+            zone_pct: rate(DisciplineColumn::Zone)?,
+            fstrike_pct: optional_rate(DisciplineColumn::FStrike)?,
+            swstr_pct: optional_rate(DisciplineColumn::SwStr)?,
+            cstr_pct: optional_rate(DisciplineColumn::CStr)?,
+            csw_pct: optional_rate(DisciplineColumn::Csw)?,
+        })
+    }
+
+    // The player's row of the table. Names are compared after normalizing, so accents,
+    // punctuation and suffixes like "Jr." do not have to match.
+    pub fn get(&self, player_name: &str) -> Result<PlateDiscipline> {
+        let rows = match self.names.get(&normalize_name(player_name)) {
+            Some(rows) => rows,
+            None => {
+                return Err(Error::MissingDiscipline {
+                    name: player_name.to_owned(),
+                    year: self.year,
+                })
+            }
+        };
+        if rows.len() > 1 {
+            let name_column = self.columns[&DisciplineColumn::Name];
+            let team_column = self.columns.get(&DisciplineColumn::Team);
+            return Err(Error::AmbiguousName {
+                name: player_name.to_owned(),
+                candidates: rows
+                    .iter()
+                    .map(|row| {
+                        let (line, fields) = &self.rows[*row];
+                        let team = team_column
+                            .and_then(|index| fields.get(*index))
+                            .cloned()
+                            .unwrap_or_else(|| format!("line {}", line));
+                        format!("{} ({})", fields[name_column], team)
+                    })
+                    .collect(),
+            });
+        }
+        let (line, fields) = &self.rows[rows[0]];
+        self.parse_row(*line, fields)
+    }
+
+    // The row with the FanGraphs `playerid`, only present in tables exported with that column
+    pub fn get_by_fangraphs_id(&self, fangraphs_id: &str) -> Result<PlateDiscipline> {
+        match self.fangraphs_ids.get(fangraphs_id) {
+            Some(row) => {
+                let (line, fields) = &self.rows[*row];
+                self.parse_row(*line, fields)
+            }
+            None => Err(Error::MissingDiscipline {
+                name: format!("FanGraphs player {}", fangraphs_id),
+                year: self.year,
+            }),
+        }
+    }
+}
+
+// Load the season's `<year>_plate_discipline.csv`
+pub fn read_plate_discipline_table(data_dir: &Path, year: i32) -> Result<DisciplineTable> {
+    let path_in = season_directory(data_dir, year).join(format!("{year}_plate_discipline.csv"));
+    let contents = fs::read_to_string(&path_in).map_err(|e| Error::io(&path_in, e))?;
+    DisciplineTable::parse(&contents, &path_in.display().to_string(), year)
+}
+
+// The batters' table split by the pitcher's hand, `<year>_plate_discipline_vs_L.csv` or
+// `<year>_plate_discipline_vs_R.csv`
+pub fn platoon_plate_discipline_path(data_dir: &Path, year: i32, pitcher: Hand) -> PathBuf {
+    season_directory(data_dir, year).join(format!(
+        "{year}_plate_discipline_vs_{}.csv",
+        pitcher.to_code()
+    ))
+}
+
+pub fn read_platoon_plate_discipline_table(
+    data_dir: &Path,
+    year: i32,
+    pitcher: Hand,
+) -> Result<DisciplineTable> {
+    let path_in = platoon_plate_discipline_path(data_dir, year, pitcher);
+    let contents = fs::read_to_string(&path_in).map_err(|e| Error::io(&path_in, e))?;
+    DisciplineTable::parse(&contents, &path_in.display().to_string(), year)
+}
+
+// The same table for pitchers, `<year>_pitcher_plate_discipline.csv`
+pub fn pitcher_plate_discipline_path(data_dir: &Path, year: i32) -> PathBuf {
+    season_directory(data_dir, year).join(format!("{year}_pitcher_plate_discipline.csv"))
+}
+
+pub fn read_pitcher_plate_discipline_table(data_dir: &Path, year: i32) -> Result<DisciplineTable> {
+    let path_in = pitcher_plate_discipline_path(data_dir, year);
+    let contents = fs::read_to_string(&path_in).map_err(|e| Error::io(&path_in, e))?;
+    DisciplineTable::parse(&contents, &path_in.display().to_string(), year)
+}
+
+// Look up the player's row in the season's FanGraphs plate discipline table
+pub fn read_plate_discipline_from_file(
+    data_dir: &Path,
+    player_name: &str,
+    year: i32,
+) -> Result<PlateDiscipline> {
+    read_plate_discipline_table(data_dir, year)?.get(player_name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(contents: &str) -> Result<DisciplineTable> {
+        DisciplineTable::parse(contents, "2004_plate_discipline.csv", 2004)
+    }
+
+    fn rates(contents: &str, name: &str) -> (f32, f32, f32) {
+        parse(contents)
+            .unwrap()
+            .get(name)
+            .unwrap()
+            .simulation_rates()
+    }
+
+    fn assert_close(actual: (f32, f32, f32), expected: (f32, f32, f32)) {
+        let close = |a: f32, b: f32| (a - b).abs() < 1e-4;
+        assert!(
+            close(actual.0, expected.0)
+                && close(actual.1, expected.1)
+                && close(actual.2, expected.2),
+            "{:?} != {:?}",
+            actual,
+            expected
+        );
+    }
+
+    #[test]
+    fn tab_and_comma_tables_read_the_same() {
+        let tab = "Name\tTeam\tO-Swing%\tSwing%\tZone%\n\
+                   Barry Bonds\tSFG\t12.4 %\t29.6 %\t45.2 %\n\
+                   Lyle Overbay\tMIL\t22.0 %\t45.1 %\t51.3 %\n";
+        let comma = tab.replace('\t', ",");
+        for name in ["Barry Bonds", "Lyle Overbay"] {
+            assert_eq!(
+                parse(tab).unwrap().get(name).unwrap(),
+                parse(&comma).unwrap().get(name).unwrap()
+            );
+        }
+        assert_close(rates(tab, "Barry Bonds"), (12.4, 29.6, 45.2));
+    }
+
+    #[test]
+    fn quoted_fields_keep_their_commas() {
+        let table = "Name,Team,O-Swing%,Swing%,Zone%\n\
+                     \"Griffey, Ken\",CIN,20.1%,44.0%,\"45.2%\"\n";
+        assert_close(rates(table, "Griffey, Ken"), (20.1, 44.0, 45.2));
+        assert_eq!(
+            split_fields("\"Say \"\"Hey\"\" Kid\", \"a,b\" ,c", ','),
+            ["Say \"Hey\" Kid", "a,b", "c"]
+        );
+    }
+
+    #[test]
+    fn percentages_with_or_without_a_sign_or_as_fractions() {
+        let header = "Name,O-Swing%,Swing%,Zone%\n";
+        let cases = [
+            "Barry Bonds,12.4 %,29.6 %,45.2 %",
+            "Barry Bonds,12.4%,29.6%,45.2%",
+            "Barry Bonds,12.4,29.6,45.2",
+            "Barry Bonds,0.124,0.296,0.452",
+        ];
+        for row in cases {
+            assert_close(
+                rates(&format!("{}{}\n", header, row), "Barry Bonds"),
+                (12.4, 29.6, 45.2),
+            );
+        }
+    }
+
+    #[test]
+    fn fangraphs_export_headers() {
+        let table = "\u{feff}\"Name\",\"Team\",\"O-Swing%\",\"Z-Swing%\",\"Swing%\",\
+                     \"O-Contact%\",\"Z-Contact%\",\"Contact%\",\"Zone%\",\"F-Strike%\",\
+                     \"SwStr%\",\"CStr%\",\"CSW%\",\"NameASCII\",\"PlayerId\",\"MLBAMID\"\n\
+                     \"Barry Bonds\",\"SFG\",\"0.124\",\"0.502\",\"0.296\",\"0.682\",\"0.933\",\
+                     \"0.861\",\"0.452\",\"0.561\",\"0.041\",\"0.184\",\"0.225\",\
+                     \"Barry Bonds\",\"1109\",\"111188\"\n";
+        let table = parse(table).unwrap();
+        let bonds = table.get_by_fangraphs_id("1109").unwrap();
+        assert_eq!(bonds.get_team(), &Some("SFG".to_owned()));
+        assert_close(bonds.simulation_rates(), (12.4, 29.6, 45.2));
+        assert!((bonds.get_csw_pct().unwrap() - 22.5).abs() < 1e-4);
+        assert_eq!(table.get("Barry Bonds").unwrap(), bonds);
+    }
+
+    #[test]
+    fn leaderboard_tables_without_a_header() {
+        let table = "1\tBarry Bonds\tSFG\t12.4 %\t50.2 %\t29.6 %\t68.2 %\t93.3 %\t86.1 %\t45.2 %\n";
+        assert_close(rates(table, "Barry Bonds"), (12.4, 29.6, 45.2));
+    }
+
+    #[test]
+    fn header_missing_a_required_column_is_an_error() {
+        let cases = [
+            "Name,Team,O-Swing%,Swing%\nBarry Bonds,SFG,12.4%,29.6%\n",
+            "Team,O-Swing%,Swing%,Zone%\nSFG,12.4%,29.6%,45.2%\n",
+            "Name\tTeam\tZ-Swing%\tSwing%\tZone%\nBarry Bonds\tSFG\t50.2%\t29.6%\t45.2%\n",
+        ];
+        for table in cases {
+            assert!(
+                matches!(parse(table), Err(Error::MalformedRecord { line: 1, .. })),
+                "{}",
+                table
+            );
+        }
+    }
+}
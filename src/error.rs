@@ -0,0 +1,147 @@
+use std::fmt;
+use std::path::PathBuf;
+
+#[derive(Debug)]
+pub enum Error {
+    // A data file could not be opened or read
+    Io {
+        path: PathBuf,
+        source: std::io::Error,
+    },
+    // A season's zip archive could not be opened or an entry in it could not be found
+    Archive {
+        path: PathBuf,
+        source: zip::result::ZipError,
+    },
+    // A line in a data file could not be understood
+    MalformedRecord {
+        file: String,
+        line: usize,
+        message: String,
+    },
+    // A value (date, percentage, ...) that could not be parsed on its own
+    InvalidValue {
+        value: String,
+        expected: &'static str,
+    },
+    // The player could not be found on the roster, or on any roster when no team was given
+    UnknownPlayer {
+        name: String,
+        team: Option<String>,
+        year: i32,
+    },
+    // The player is on no roster of any of the seasons asked for, None when there were none
+    UnknownPlayerInSeasons {
+        name: String,
+        years: Option<(i32, i32)>,
+    },
+    // The player has no row in the season's plate discipline table, usually a pitcher
+    MissingDiscipline {
+        name: String,
+        year: i32,
+    },
+    // More than one player matches the name
+    AmbiguousName {
+        name: String,
+        candidates: Vec<String>,
+    },
+    // The guts table has no row for the season
+    MissingLinearWeights {
+        year: i32,
+    },
+    // Pitcher zone rates were asked for from a table the season does not have
+    MissingPitcherDiscipline {
+        year: i32,
+    },
+}
+
+pub type Result<T> = std::result::Result<T, Error>;
+
+impl Error {
+    pub fn io(path: impl Into<PathBuf>, source: std::io::Error) -> Self {
+        Error::Io {
+            path: path.into(),
+            source,
+        }
+    }
+    pub fn archive(path: impl Into<PathBuf>, source: zip::result::ZipError) -> Self {
+        Error::Archive {
+            path: path.into(),
+            source,
+        }
+    }
+    pub fn malformed(file: &str, line: usize, message: impl Into<String>) -> Self {
+        Error::MalformedRecord {
+            file: file.to_owned(),
+            line,
+            message: message.into(),
+        }
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Error::Io { path, source } => {
+                write!(f, "could not read {}: {}", path.display(), source)
+            }
+            Error::Archive { path, source } => {
+                write!(f, "could not read archive {}: {}", path.display(), source)
+            }
+            Error::MalformedRecord {
+                file,
+                line,
+                message,
+            } => write!(f, "{}:{}: {}", file, line, message),
+            Error::InvalidValue { value, expected } => {
+                write!(f, "could not parse {:?} as {}", value, expected)
+            }
+            Error::UnknownPlayer {
+                name,
+                team: Some(team),
+                year,
+            } => write!(f, "{} not found on the {} {} roster", name, year, team),
+            Error::UnknownPlayer {
+                name,
+                team: None,
+                year,
+            } => write!(f, "{} not found on any {} roster", name, year),
+            Error::UnknownPlayerInSeasons {
+                name,
+                years: Some((first, last)),
+            } => write!(
+                f,
+                "{} not found on any roster from {} to {}",
+                name, first, last
+            ),
+            Error::UnknownPlayerInSeasons { name, years: None } => {
+                write!(f, "{} not found, there are no seasons to look in", name)
+            }
+            Error::MissingDiscipline { name, year } => {
+                write!(f, "no {} plate discipline data for {}", year, name)
+            }
+            Error::AmbiguousName { name, candidates } => write!(
+                f,
+                "{} matches more than one player: {}",
+                name,
+                candidates.join(", ")
+            ),
+            Error::MissingLinearWeights { year } => {
+                write!(f, "no linear weights for {}", year)
+            }
+            Error::MissingPitcherDiscipline { year } => {
+                write!(f, "no pitcher plate discipline table for {}", year)
+            }
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::Io { source, .. } => Some(source),
+            Error::Archive { source, .. } => Some(source),
+            _ => None,
+        }
+    }
+}
@@ -0,0 +1,355 @@
+use std::fs;
+use std::path::Path;
+
+use crate::codes::{parse_pitch_codes, PlateOutcome};
+use crate::error::{Error, Result};
+use crate::game_state::{GameState, Half};
+use crate::game_type::GameType;
+use crate::pitch_sequence::PitchSequence;
+use crate::roster::Hand;
+use crate::season_file::SeasonFile;
+use crate::{convert_string_to_date, list_season_files, Date, PlateAppearance};
+
+// Read every plate appearance in a Retrosheet event file (.EVA/.EVN), along with a warning for
+// each record that had to be skipped
+pub fn read_plate_appearances_from_file(path: &Path) -> Result<(Vec<PlateAppearance>, Vec<Error>)> {
+    let contents = fs::read_to_string(path).map_err(|e| Error::io(path, e))?;
+    Ok(parse_plate_appearances(
+        &contents,
+        &path.display().to_string(),
+    ))
+}
+
+// Read every plate appearance in one of the season's event files, wherever it is kept
+pub fn read_plate_appearances_from_season_file(
+    file: &SeasonFile,
+) -> Result<(Vec<PlateAppearance>, Vec<Error>)> {
+    Ok(parse_plate_appearances(
+        &file.read_to_string()?,
+        &file.to_string(),
+    ))
+}
+
+// Read a batter's plate appearances from every event file of the season. Files that cannot be
+// read are skipped like malformed records and come back among the warnings.
+pub fn read_season_plate_appearances(
+    data_dir: &Path,
+    year: i32,
+    player_id: &str,
+) -> Result<(Vec<PlateAppearance>, Vec<Error>)> {
+    let mut plate_appearances: Vec<PlateAppearance> = Vec::new();
+    let mut warnings: Vec<Error> = Vec::new();
+    for file in list_season_files(data_dir, year)? {
+        if !is_event_file(&file) {
+            continue;
+        }
+        match read_plate_appearances_from_season_file(&file) {
+            Ok((appearances, file_warnings)) => {
+                plate_appearances.extend(
+                    appearances
+                        .into_iter()
+                        .filter(|appearance| appearance.get_batter() == player_id),
+                );
+                warnings.extend(file_warnings);
+            }
+            Err(e) => warnings.push(e),
+        }
+    }
+    Ok((plate_appearances, warnings))
+}
+
+// Regular season .EVA and .EVN files, and .EVE files for the postseason and All-Star games
+pub fn is_event_file(file: &SeasonFile) -> bool {
+    matches!(file.extension(), Some("EVA") | Some("EVN") | Some("EVE"))
+}
+
+// Replay every game in the event file contents, attaching the game situation to each plate
+// appearance as it happens. `file` labels warnings and tells the game type of games without a
+// `gametype` record. A record that cannot be understood is skipped with a warning and the
+// replay carries on from the next one.
+pub fn parse_plate_appearances(contents: &str, file: &str) -> (Vec<PlateAppearance>, Vec<Error>) {
+    let mut plate_appearances: Vec<PlateAppearance> = Vec::new();
+    let mut warnings: Vec<Error> = Vec::new();
+    let file_game_type = GameType::from_file_name(file);
+    let mut game_type = file_game_type;
+    let mut last_game_date = Date::new(0, 0, 0);
+    let mut visiting_team = String::new();
+    let mut home_team = String::new();
+    let mut umpire = String::new();
+    // `badj` and `padj` records: a batter or pitcher using his other hand for the next plate
+    // appearance
+    let mut batter_adjustment: Option<(String, Hand)> = None;
+    let mut pitcher_adjustment: Option<(String, Hand)> = None;
+    let mut game_state = GameState::new();
+    // Pitcher on the mound for the visitors and the home team
+    let mut pitchers = [String::new(), String::new()];
+    let mut pending_runners: Vec<usize> = Vec::new();
+    // Plays not involving the batter since the last plate appearance ended, which show up as `.`
+    // in its pitch sequence, and the situation before the first of them
+    let mut plays_during_appearance: Vec<String> = Vec::new();
+    let mut appearance_start: Option<GameState> = None;
+    // First plate appearance of the half inning being replayed
+    let mut half_inning_start = 0;
+    for (line_index, line) in contents.lines().enumerate() {
+        let malformed = |message: String| Error::malformed(file, line_index + 1, message);
+        let line_data = line.trim_end().split(',').collect::<Vec<&str>>();
+        match line_data[0] {
+            "id" => {
+                finish_half_inning(&mut plate_appearances[half_inning_start..], &game_state);
+                half_inning_start = plate_appearances.len();
+                game_state = GameState::new();
+                pitchers = [String::new(), String::new()];
+                game_type = file_game_type;
+                umpire.clear();
+                batter_adjustment = None;
+                pitcher_adjustment = None;
+                pending_runners.clear();
+                plays_during_appearance.clear();
+                appearance_start = None;
+            }
+            "start" | "sub" => {
+                // id,"name",team,batting order,position. Names can hold a comma, so the rest
+                // is counted from the end.
+                if line_data.len() < 6 {
+                    warnings.push(malformed(format!("bad lineup record {:?}", line)));
+                    continue;
+                }
+                let position = line_data[line_data.len() - 1];
+                let team = line_data[line_data.len() - 3];
+                if position == "1" {
+                    let side = match team {
+                        "0" => 0,
+                        "1" => 1,
+                        _ => {
+                            warnings.push(malformed(format!("bad team flag {:?}", team)));
+                            continue;
+                        }
+                    };
+                    pitchers[side] = line_data[1].to_owned();
+                }
+            }
+            "info" if line_data.len() > 2 && line_data[1] == "date" => {
+                match convert_string_to_date(line_data[2]) {
+                    Ok(date) => last_game_date = date,
+                    Err(e) => warnings.push(malformed(e.to_string())),
+                }
+            }
+            "info" if line_data.len() > 2 && line_data[1] == "gametype" => {
+                game_type = GameType::from_info(line_data[2]);
+            }
+            "info" if line_data.len() > 2 && line_data[1] == "visteam" => {
+                visiting_team = line_data[2].to_owned();
+            }
+            "info" if line_data.len() > 2 && line_data[1] == "hometeam" => {
+                home_team = line_data[2].to_owned();
+            }
+            // Some older games have no umpire listed, or `(none)`
+            "info"
+                if line_data.len() > 2
+                    && line_data[1] == "umphome"
+                    && !line_data[2].is_empty()
+                    && !line_data[2].starts_with('(') =>
+            {
+                umpire = line_data[2].to_owned();
+            }
+            "badj" | "padj" => {
+                let adjustment = match (line_data.get(1), line_data.get(2)) {
+                    (Some(player), Some(hand)) => match Hand::from_code(hand) {
+                        Some(hand) => (player.to_string(), hand),
+                        None => {
+                            warnings.push(malformed(format!("bad hand {:?}", hand)));
+                            continue;
+                        }
+                    },
+                    _ => {
+                        warnings.push(malformed(format!("bad hand adjustment {:?}", line)));
+                        continue;
+                    }
+                };
+                if line_data[0] == "badj" {
+                    batter_adjustment = Some(adjustment);
+                } else {
+                    pitcher_adjustment = Some(adjustment);
+                }
+            }
+            "radj" => {
+                // Runners placed on base to start extra innings, applied with the next play
+                match line_data.get(2).and_then(|base| base.parse::<usize>().ok()) {
+                    Some(base) => pending_runners.push(base),
+                    None => warnings.push(malformed(format!("bad runner adjustment {:?}", line))),
+                }
+            }
+            "play" => {
+                if line_data.len() < 7 {
+                    warnings.push(malformed(format!(
+                        "play record has {} fields, expected 7",
+                        line_data.len()
+                    )));
+                    continue;
+                }
+                let inning = match line_data[1].parse::<i32>() {
+                    Ok(inning) => inning,
+                    Err(_) => {
+                        warnings.push(malformed(format!("bad inning {:?}", line_data[1])));
+                        continue;
+                    }
+                };
+                let half = match Half::from_retrosheet_flag(line_data[2]) {
+                    Some(half) => half,
+                    None => {
+                        warnings.push(malformed(format!(
+                            "bad home/visitor flag {:?}",
+                            line_data[2]
+                        )));
+                        continue;
+                    }
+                };
+                if game_state.get_inning() != &inning || game_state.get_half() != &half {
+                    finish_half_inning(&mut plate_appearances[half_inning_start..], &game_state);
+                    half_inning_start = plate_appearances.len();
+                    // A plate appearance cut short by the third out starts over next time up
+                    plays_during_appearance.clear();
+                    appearance_start = None;
+                }
+                game_state.start_half_inning(inning, half);
+                for base in pending_runners.drain(..) {
+                    game_state.place_runner(base);
+                }
+                let state_before_play = game_state.clone();
+                let event = line_data[6];
+                game_state.apply_event(event);
+                let state_after_play = game_state.clone();
+                let (batting_team, pitcher) = match half {
+                    Half::Top => (&visiting_team, &pitchers[1]),
+                    Half::Bottom => (&home_team, &pitchers[0]),
+                };
+                let plate_appearance = plate_appearance_from_play(
+                    &line_data,
+                    &last_game_date,
+                    batting_team,
+                    state_before_play.clone(),
+                );
+                if let Some(plate_appearance) = plate_appearance {
+                    let sequence = PitchSequence::parse(line_data[5])
+                        .with_plays(plays_during_appearance.drain(..));
+                    let mut plate_appearance = plate_appearance
+                        .with_game_type(game_type)
+                        .with_sequence(sequence)
+                        .with_game_state_at_start(
+                            appearance_start.take().unwrap_or(state_before_play),
+                        )
+                        .with_game_state_after(state_after_play);
+                    if !pitcher.is_empty() {
+                        plate_appearance = plate_appearance.with_pitcher(pitcher.to_owned());
+                    }
+                    if !umpire.is_empty() {
+                        plate_appearance = plate_appearance.with_umpire(umpire.clone());
+                    }
+                    if let Some((batter, hand)) = batter_adjustment.take() {
+                        if &batter == plate_appearance.get_batter() {
+                            plate_appearance = plate_appearance.with_batter_hand(hand);
+                        }
+                    }
+                    if let Some((adjusted_pitcher, hand)) = pitcher_adjustment.take() {
+                        if &adjusted_pitcher == pitcher {
+                            plate_appearance = plate_appearance.with_pitcher_hand(hand);
+                        }
+                    }
+                    plate_appearances.push(plate_appearance);
+                } else {
+                    appearance_start.get_or_insert(state_before_play);
+                    plays_during_appearance.push(event.to_owned());
+                }
+            }
+            _ => {}
+        }
+    }
+    finish_half_inning(&mut plate_appearances[half_inning_start..], &game_state);
+    (plate_appearances, warnings)
+}
+
+// Once a half inning is over, record how many runs scored from the final play of each of its plate
+// appearances on. Half innings cut short by a walk-off or the end of a game are left unknown.
+fn finish_half_inning(plate_appearances: &mut [PlateAppearance], end_of_inning: &GameState) {
+    let complete = end_of_inning.get_outs() == &3;
+    for appearance in plate_appearances {
+        let runs = end_of_inning.get_batting_team_score()
+            - appearance.get_game_state().get_batting_team_score();
+        appearance.set_runs_to_end_of_inning(Some(runs).filter(|_| complete));
+    }
+}
+
+// The count field of a play record, like `21`. Older seasons write `??` when it is unknown.
+fn parse_count(count: &str) -> Option<(i32, i32)> {
+    let mut digits = count.chars().map(|digit| digit.to_digit(10));
+    match (digits.next(), digits.next(), digits.next()) {
+        (Some(Some(balls)), Some(Some(strikes)), None) if balls < 4 && strikes < 3 => {
+            Some((balls as i32, strikes as i32))
+        }
+        _ => None,
+    }
+}
+
+fn plate_appearance_from_play(
+    line_data: &[&str],
+    date: &Date,
+    team: &str,
+    game_state: GameState,
+) -> Option<PlateAppearance> {
+    let outcome = PlateOutcome::from_event(line_data[6])?;
+    let plate_appearance = PlateAppearance::new(
+        date.clone(),
+        line_data[3].to_owned(),
+        team.to_owned(),
+        outcome,
+        parse_pitch_codes(line_data[5]),
+        line_data[6].to_owned(),
+        game_state,
+    );
+    Some(match parse_count(line_data[4]) {
+        Some((balls, strikes)) => plate_appearance.with_count(balls, strikes),
+        None => plate_appearance,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const GAME: &str = "id,SFN200404120
+info,visteam,MIL
+info,hometeam,SFN
+info,date,2004/04/12
+start,kinnm002,\"Matt Kinney\",0,9,1
+start,willj003,\"Jerome Williams\",1,9,1
+play,1,0,podss001,21,CBBX,63/G-
+play,1,0,counc001,21,CBBX
+play,x,0,spivj001,20,BBX,S7/G
+play,1,0,jenkg001,01,CX,S9/G
+play,1,1,durhr001,30,BBBB,W
+";
+
+    #[test]
+    fn malformed_records_are_skipped_with_a_warning() {
+        let (plate_appearances, warnings) = parse_plate_appearances(GAME, "2004SFN.EVN");
+        let batters = plate_appearances
+            .iter()
+            .map(|appearance| appearance.get_batter().as_str())
+            .collect::<Vec<&str>>();
+        assert_eq!(batters, ["podss001", "jenkg001", "durhr001"]);
+        let lines = warnings
+            .iter()
+            .map(|warning| match warning {
+                Error::MalformedRecord { file, line, .. } => (file.as_str(), *line),
+                e => panic!("unexpected warning {}", e),
+            })
+            .collect::<Vec<(&str, usize)>>();
+        assert_eq!(lines, [("2004SFN.EVN", 8), ("2004SFN.EVN", 9)]);
+        // The replay carries on with the game situation it had
+        assert_eq!(*plate_appearances[1].get_game_state().get_outs(), 1);
+        assert_eq!(
+            *plate_appearances[1].get_pitcher(),
+            Some("willj003".to_owned())
+        );
+    }
+}
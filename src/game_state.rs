@@ -0,0 +1,447 @@
+use std::fmt;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Half {
+    Top,
+    Bottom,
+}
+
+impl Half {
+    // Retrosheet play records use 0 for the visiting team batting and 1 for the home team
+    pub fn from_retrosheet_flag(flag: &str) -> Option<Half> {
+        match flag {
+            "0" => Some(Half::Top),
+            "1" => Some(Half::Bottom),
+            _ => None,
+        }
+    }
+}
+
+// The situation when a plate appearance starts: inning, outs, who is on base and the score
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct GameState {
+    inning: i32,
+    half: Half,
+    outs: i32,
+    bases: [bool; 3],
+    visitor_score: i32,
+    home_score: i32,
+}
+
+impl Default for GameState {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl GameState {
+    pub fn new() -> Self {
+        Self {
+            inning: 1,
+            half: Half::Top,
+            outs: 0,
+            bases: [false; 3],
+            visitor_score: 0,
+            home_score: 0,
+        }
+    }
+    pub fn get_inning(&self) -> &i32 {
+        &self.inning
+    }
+    pub fn get_half(&self) -> &Half {
+        &self.half
+    }
+    pub fn get_outs(&self) -> &i32 {
+        &self.outs
+    }
+    // Occupancy of first, second and third base
+    pub fn get_bases(&self) -> &[bool; 3] {
+        &self.bases
+    }
+    pub fn get_visitor_score(&self) -> &i32 {
+        &self.visitor_score
+    }
+    pub fn get_home_score(&self) -> &i32 {
+        &self.home_score
+    }
+    pub fn get_batting_team_score(&self) -> i32 {
+        match self.half {
+            Half::Top => self.visitor_score,
+            Half::Bottom => self.home_score,
+        }
+    }
+    pub fn get_fielding_team_score(&self) -> i32 {
+        match self.half {
+            Half::Top => self.home_score,
+            Half::Bottom => self.visitor_score,
+        }
+    }
+    pub fn runners_on(&self) -> i32 {
+        self.bases.iter().filter(|occupied| **occupied).count() as i32
+    }
+
+    // Move to a new half inning if the play record belongs to one. Outs and bases are cleared,
+    // the score carries over.
+    pub fn start_half_inning(&mut self, inning: i32, half: Half) {
+        if self.inning != inning || self.half != half {
+            self.inning = inning;
+            self.half = half;
+            self.outs = 0;
+            self.bases = [false; 3];
+        }
+    }
+
+    // Place a runner directly on a base, used for `radj` records (extra-inning runner on second)
+    pub fn place_runner(&mut self, base: usize) {
+        if (1..=3).contains(&base) {
+            self.bases[base - 1] = true;
+        }
+    }
+
+    // Replay a Retrosheet event string (e.g. `S8/G.2-H;1-3`) and update outs, bases and score.
+    // Returns the number of runs that scored on the play.
+    pub fn apply_event(&mut self, event: &str) -> i32 {
+        let moves = runner_moves_for_event(event, &self.bases);
+        let mut bases = [false; 3];
+        let mut runs = 0;
+        let mut outs = 0;
+        // Index 0 is the batter, 1-3 are the runners on first, second and third
+        for (origin, runner_move) in moves.iter().enumerate() {
+            if origin > 0 && !self.bases[origin - 1] {
+                continue;
+            }
+            match runner_move {
+                RunnerMove::Stay => {
+                    if origin > 0 {
+                        bases[origin - 1] = true;
+                    }
+                }
+                RunnerMove::Advance(4) => runs += 1,
+                RunnerMove::Advance(base @ 1..=3) => bases[*base as usize - 1] = true,
+                // Not produced by the parser, nobody moves backwards to the plate
+                RunnerMove::Advance(_) => {}
+                RunnerMove::Out => outs += 1,
+            }
+        }
+        self.outs = std::cmp::min(self.outs + outs, 3);
+        self.bases = if self.outs == 3 { [false; 3] } else { bases };
+        match self.half {
+            Half::Top => self.visitor_score += runs,
+            Half::Bottom => self.home_score += runs,
+        }
+        runs
+    }
+}
+
+impl fmt::Display for GameState {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let half = match self.half {
+            Half::Top => "Top",
+            Half::Bottom => "Bot",
+        };
+        let bases = self
+            .bases
+            .iter()
+            .enumerate()
+            .map(|(i, occupied)| {
+                if *occupied {
+                    char::from(b'1' + i as u8)
+                } else {
+                    '-'
+                }
+            })
+            .collect::<String>();
+        write!(
+            f,
+            "{} {}, {} out, bases {}, {}-{}",
+            half, self.inning, self.outs, bases, self.visitor_score, self.home_score
+        )
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum RunnerMove {
+    Stay,
+    Advance(u8), // destination base, 4 is home
+    Out,
+}
+
+// Base codes in event strings: B is the batter, 1-3 the bases and H home
+fn base_from_char(base: char) -> Option<u8> {
+    match base {
+        'B' => Some(0),
+        '1' => Some(1),
+        '2' => Some(2),
+        '3' => Some(3),
+        'H' => Some(4),
+        _ => None,
+    }
+}
+
+// Events whose implied movement is handled by `apply_baserunning_play`
+const BASERUNNING_EVENTS: [&str; 3] = ["SB", "CS", "PO"];
+// Events that move nobody unless the advances say so
+const NO_ADVANCE_EVENTS: [&str; 7] = ["WP", "PB", "BK", "DI", "OA", "NP", "FLE"];
+
+// Retrosheet plays that end a plate appearance, as opposed to baserunning events (stolen bases,
+// wild pitches, pickoffs, ...) that happen while the batter is still at the plate
+pub fn is_batter_event(event: &str) -> bool {
+    let basic = basic_play(event);
+    let primary = basic.split('+').next().unwrap_or("");
+    !primary.is_empty()
+        && !BASERUNNING_EVENTS
+            .iter()
+            .chain(NO_ADVANCE_EVENTS.iter())
+            .any(|e| primary.starts_with(e))
+}
+
+// The part of the event before the modifiers and advances, with uncertainty markers removed
+pub(crate) fn basic_play(event: &str) -> String {
+    let end = event.find(['/', '.']).unwrap_or(event.len());
+    event[..end]
+        .chars()
+        .filter(|c| *c != '!' && *c != '#' && *c != '?')
+        .collect::<String>()
+}
+
+fn runner_moves_for_event(event: &str, bases: &[bool; 3]) -> [RunnerMove; 4] {
+    let mut moves = [RunnerMove::Stay; 4];
+    let mut forced = false;
+    let basic = basic_play(event);
+    for (i, play) in basic.split('+').enumerate() {
+        for play in play.split(';') {
+            if i == 0 {
+                forced |= apply_batter_play(play, &mut moves);
+            } else {
+                apply_baserunning_play(play, &mut moves);
+            }
+        }
+    }
+
+    // Explicit advances override anything implied by the basic play
+    let mut explicit = [false; 4];
+    if let Some(advance_start) = event.find('.') {
+        for advance in event[advance_start + 1..].split(';') {
+            let advance = advance.trim();
+            let chars = advance.chars().collect::<Vec<char>>();
+            if chars.len() < 3 {
+                continue;
+            }
+            // Only the batter is written as B, a runner can never be sent back to him
+            let (origin, destination) = match (base_from_char(chars[0]), base_from_char(chars[2])) {
+                (Some(origin), Some(destination)) if origin < 4 && destination > 0 => {
+                    (origin, destination)
+                }
+                _ => continue,
+            };
+            // A runner thrown out is safe after all when every throw credited to the play was an
+            // error. `1X2(4E6)(6)` is an error followed by a putout, so he is out.
+            let fielding = fielding_groups(&advance[3..]);
+            let safe_on_error =
+                !fielding.is_empty() && fielding.iter().all(|fielding| fielding.contains('E'));
+            moves[origin as usize] = if chars[1] == 'X' && !safe_on_error {
+                RunnerMove::Out
+            } else {
+                RunnerMove::Advance(destination)
+            };
+            explicit[origin as usize] = true;
+        }
+    }
+
+    // Walks, hit batters and interference push forced runners along when the advance is not
+    // written out
+    if forced {
+        for base in 1..=3 {
+            let batter_or_runner_behind_forced = base == 1
+                || (bases[base - 2] && moves[base - 1] == RunnerMove::Advance(base as u8));
+            if bases[base - 1]
+                && !explicit[base]
+                && moves[base] == RunnerMove::Stay
+                && batter_or_runner_behind_forced
+            {
+                moves[base] = RunnerMove::Advance(base as u8 + 1);
+            }
+        }
+    }
+    moves
+}
+
+// The fielding credits in parentheses after an advance, like `4E6` and `6` in `(4E6)(6)`,
+// leaving out modifiers such as `(UR)` or `(NR)`
+fn fielding_groups(modifiers: &str) -> Vec<&str> {
+    modifiers
+        .split('(')
+        .skip(1)
+        .filter_map(|group| group.split(')').next())
+        .filter(|group| group.starts_with(|c: char| c.is_ascii_digit() || c == 'E'))
+        .collect()
+}
+
+// Sets the implied movement of the batter and runners for the primary play of an event.
+// Returns true if the play forces runners to advance.
+fn apply_batter_play(play: &str, moves: &mut [RunnerMove; 4]) -> bool {
+    if BASERUNNING_EVENTS.iter().any(|e| play.starts_with(e)) {
+        apply_baserunning_play(play, moves);
+        return false;
+    }
+    if NO_ADVANCE_EVENTS.iter().any(|e| play.starts_with(e)) {
+        return false;
+    }
+    if play.starts_with("HP") || play.starts_with("IW") || play.starts_with('W') {
+        moves[0] = RunnerMove::Advance(1);
+        return true;
+    }
+    if play.starts_with('I') || play.starts_with('C') {
+        moves[0] = RunnerMove::Advance(1);
+        return true;
+    }
+    if play.starts_with("DGR") || play.starts_with('D') {
+        moves[0] = RunnerMove::Advance(2);
+    } else if play.starts_with('S') {
+        moves[0] = RunnerMove::Advance(1);
+    } else if play.starts_with('T') {
+        moves[0] = RunnerMove::Advance(3);
+    } else if play.starts_with('H') {
+        moves[0] = RunnerMove::Advance(4);
+    } else if play.starts_with('K') {
+        moves[0] = RunnerMove::Out;
+    } else if play.starts_with('E') || play.starts_with("FC") {
+        moves[0] = RunnerMove::Advance(1);
+    } else if play.starts_with(|c: char| c.is_ascii_digit()) {
+        apply_fielding_play(play, moves);
+    }
+    false
+}
+
+// Fielded outs like `63`, `64(1)3` or `8(B)84(2)`. Parenthesized bases are runners put out,
+// a trailing fielder without parentheses means the batter was retired.
+fn apply_fielding_play(play: &str, moves: &mut [RunnerMove; 4]) {
+    let mut batter_out = false;
+    let mut rest = play;
+    while let Some(open) = rest.find('(') {
+        let close = match rest[open..].find(')') {
+            Some(close) => open + close,
+            None => break,
+        };
+        if let Some(base) = rest[open + 1..close]
+            .chars()
+            .next()
+            .and_then(base_from_char)
+        {
+            if base == 0 {
+                batter_out = true;
+            } else if base < 4 {
+                moves[base as usize] = RunnerMove::Out;
+            }
+        }
+        rest = &rest[close + 1..];
+    }
+    if play.contains('E') {
+        moves[0] = RunnerMove::Advance(1);
+    } else if batter_out || play.ends_with(|c: char| c.is_ascii_digit()) {
+        moves[0] = RunnerMove::Out;
+    } else {
+        moves[0] = RunnerMove::Advance(1);
+    }
+}
+
+// Stolen bases, caught stealing and pickoffs, e.g. `SB2`, `CS3(25)`, `POCS2(14)`, `PO1(E3)`
+fn apply_baserunning_play(play: &str, moves: &mut [RunnerMove; 4]) {
+    let on_error = play.contains('E');
+    if let Some(base) = play.strip_prefix("SB") {
+        if let Some(destination) = base.chars().next().and_then(base_from_char) {
+            if destination > 1 {
+                moves[destination as usize - 1] = RunnerMove::Advance(destination);
+            }
+        }
+    } else if let Some(base) = play
+        .strip_prefix("POCS")
+        .or_else(|| play.strip_prefix("CS"))
+    {
+        if let Some(destination) = base.chars().next().and_then(base_from_char) {
+            if destination > 1 {
+                moves[destination as usize - 1] = if on_error {
+                    RunnerMove::Advance(destination)
+                } else {
+                    RunnerMove::Out
+                };
+            }
+        }
+    } else if let Some(base) = play.strip_prefix("PO") {
+        if let Some(origin) = base.chars().next().and_then(base_from_char) {
+            if (1..4).contains(&origin) && !on_error {
+                moves[origin as usize] = RunnerMove::Out;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Runners written as the bases they are on, like "13"
+    fn state_with_runners(bases: &str) -> GameState {
+        let mut state = GameState::new();
+        for base in bases.chars().filter_map(|base| base.to_digit(10)) {
+            state.place_runner(base as usize);
+        }
+        state
+    }
+
+    #[test]
+    fn advances_move_runners_and_score() {
+        let cases = [
+            ("", "S8/G", [true, false, false], 0, 0),
+            ("1", "S8/G.1-3", [true, false, true], 0, 0),
+            ("23", "D7/L.3-H;2-H", [false, true, false], 0, 2),
+            ("1", "W", [true, true, false], 0, 0),
+            ("1", "S9.B-2;1-H", [false, true, false], 0, 1),
+            ("123", "HR/F.3-H;2-H;1-H", [false, false, false], 0, 4),
+        ];
+        for (runners, event, bases, outs, runs) in cases {
+            let mut state = state_with_runners(runners);
+            assert_eq!(state.apply_event(event), runs, "{}", event);
+            assert_eq!(*state.get_bases(), bases, "{}", event);
+            assert_eq!(*state.get_outs(), outs, "{}", event);
+        }
+    }
+
+    #[test]
+    fn advance_to_batter_is_ignored() {
+        let moves = runner_moves_for_event("S8.1-B", &[true, false, false]);
+        assert_eq!(moves[1], RunnerMove::Stay);
+        let mut state = state_with_runners("1");
+        assert_eq!(state.apply_event("S8.1-B"), 0);
+        assert_eq!(*state.get_bases(), [true, false, false]);
+        assert_eq!(*state.get_outs(), 0);
+    }
+
+    #[test]
+    fn runner_thrown_out_unless_every_throw_was_an_error() {
+        let bases = [true, false, false];
+        let cases = [
+            ("S8.1X3(85)", RunnerMove::Out),
+            ("S8.1X3(8E5)", RunnerMove::Advance(3)),
+            ("S8.1X3(E5)(UR)", RunnerMove::Advance(3)),
+            ("S8.1X2(4E6)(6)", RunnerMove::Out),
+            ("S8.1X2(4E6)(E6)", RunnerMove::Advance(2)),
+            ("S8.1X3(NR)", RunnerMove::Out),
+        ];
+        for (event, expected) in cases {
+            assert_eq!(
+                runner_moves_for_event(event, &bases)[1],
+                expected,
+                "{}",
+                event
+            );
+        }
+    }
+
+    #[test]
+    fn fielding_groups_skip_modifiers() {
+        assert_eq!(fielding_groups("(4E6)(6)"), ["4E6", "6"]);
+        assert_eq!(fielding_groups("(E2/TH)(UR)(NR)"), ["E2/TH"]);
+        assert!(fielding_groups("").is_empty());
+    }
+}
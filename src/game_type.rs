@@ -0,0 +1,80 @@
+// What kind of game a plate appearance came from
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum GameType {
+    Regular,
+    // Wild card, division series, league championship series and World Series
+    Postseason,
+    AllStar,
+    // Exhibitions, spring training and anything else
+    Other,
+}
+
+impl GameType {
+    // The value of an `info,gametype` record
+    pub fn from_info(value: &str) -> GameType {
+        match value {
+            "regular" => GameType::Regular,
+            "allstar" => GameType::AllStar,
+            "postseason" | "playoff" | "wildcard" | "divisionseries" | "lcs" | "worldseries" => {
+                GameType::Postseason
+            }
+            _ => GameType::Other,
+        }
+    }
+
+    // Older files have no `gametype` record, but Retrosheet keeps regular season games in .EVA
+    // and .EVN files and the rest in .EVE files named by series, like `2002WS.EVE` or
+    // `2002AS.EVE` for the All-Star game
+    pub fn from_file_name(name: &str) -> GameType {
+        let name = name.rsplit(['/', '\\', ':']).next().unwrap_or(name);
+        match name.rsplit_once('.') {
+            Some((stem, "EVE"))
+                if stem.trim_start_matches(|c: char| c.is_ascii_digit()) == "AS" =>
+            {
+                GameType::AllStar
+            }
+            Some((_, "EVE")) => GameType::Postseason,
+            _ => GameType::Regular,
+        }
+    }
+}
+
+// Which games to replay
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum GameSelection {
+    Regular,
+    Postseason,
+    AllStar,
+    All,
+}
+
+impl GameSelection {
+    pub fn name(&self) -> &'static str {
+        match self {
+            GameSelection::Regular => "regular",
+            GameSelection::Postseason => "postseason",
+            GameSelection::AllStar => "allstar",
+            GameSelection::All => "all",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<GameSelection> {
+        [
+            GameSelection::Regular,
+            GameSelection::Postseason,
+            GameSelection::AllStar,
+            GameSelection::All,
+        ]
+        .into_iter()
+        .find(|games| games.name() == name)
+    }
+
+    pub fn includes(&self, game_type: GameType) -> bool {
+        match self {
+            GameSelection::Regular => game_type == GameType::Regular,
+            GameSelection::Postseason => game_type == GameType::Postseason,
+            GameSelection::AllStar => game_type == GameType::AllStar,
+            GameSelection::All => true,
+        }
+    }
+}
@@ -1,6 +1,115 @@
-use core::panic;
 use rand::Rng;
+use std::collections::HashSet;
 use std::fmt;
+use std::path::{Path, PathBuf};
+
+pub mod codes;
+pub mod discipline;
+pub mod error;
+pub mod event_file;
+pub mod game_state;
+pub mod game_type;
+pub mod linear_weights;
+pub mod markov;
+pub mod monte_carlo;
+pub mod pitch_sequence;
+pub mod platoon;
+pub mod registry;
+pub mod roster;
+pub mod run_expectancy;
+pub mod season;
+pub mod season_file;
+pub mod stat_line;
+pub mod strategy;
+pub mod umpire;
+pub mod zone_model;
+
+pub use codes::{parse_pitch_codes, PitchCode, PlateOutcome};
+pub use discipline::{
+    read_plate_discipline_from_file, read_plate_discipline_table, DisciplineTable, PlateDiscipline,
+};
+pub use error::{Error, Result};
+pub use event_file::{
+    parse_plate_appearances, read_plate_appearances_from_file, read_season_plate_appearances,
+};
+pub use game_state::{GameState, Half};
+pub use game_type::{GameSelection, GameType};
+pub use linear_weights::{read_linear_weights, LinearWeights};
+pub use pitch_sequence::{Pitch, PitchSequence, SequenceEvent};
+pub use platoon::{PlatoonSplits, PlatoonZoneRates};
+pub use registry::{normalize_name, PlayerRegistry};
+pub use roster::{find_player_id, read_all_player_names, read_season_rosters, Hand, RosterEntry};
+pub use run_expectancy::RunExpectancy;
+pub use season::{PlayerId, Season, Stint};
+pub use season_file::SeasonFile;
+pub use stat_line::BattingLine;
+pub use strategy::{SwingPool, SwingStrategy};
+pub use umpire::UmpireZoneRates;
+pub use zone_model::{
+    CountZoneRates, PitcherAdaptation, PitcherZoneRates, PitcherZoneSource, ZoneModel,
+};
+
+// Retrosheet seasons are extracted to `<data_dir>/<year>eve/`
+pub fn season_directory(data_dir: &Path, year: i32) -> PathBuf {
+    data_dir.join(format!("{}eve", year))
+}
+
+// Retrosheet's archive of the season, `<data_dir>/<year>eve.zip`
+pub fn season_archive(data_dir: &Path, year: i32) -> PathBuf {
+    data_dir.join(format!("{}eve.zip", year))
+}
+
+// Years of every `<data_dir>/<year>eve/` directory and `<data_dir>/<year>eve.zip` archive, in
+// order
+pub fn list_season_years(data_dir: &Path) -> Result<Vec<i32>> {
+    let mut years = Vec::new();
+    for entry in std::fs::read_dir(data_dir).map_err(|e| Error::io(data_dir, e))? {
+        let path = entry.map_err(|e| Error::io(data_dir, e))?.path();
+        let name = path
+            .file_name()
+            .and_then(|name| name.to_str())
+            .unwrap_or_default();
+        let year = match (name.strip_suffix("eve"), name.strip_suffix("eve.zip")) {
+            (Some(year), _) if path.is_dir() => year,
+            (_, Some(year)) if path.is_file() => year,
+            _ => continue,
+        };
+        if let Some(year) = year.parse::<i32>().ok().filter(|_| year.len() == 4) {
+            years.push(year);
+        }
+    }
+    years.sort();
+    years.dedup();
+    Ok(years)
+}
+
+// Every file of the season, from its directory and its zip archive, whichever are there. A file
+// in the directory takes the place of the archive's copy. Sorted by name so that results do not
+// depend on the order the filesystem or the archive lists them in.
+pub fn list_season_files(data_dir: &Path, year: i32) -> Result<Vec<SeasonFile>> {
+    let season_dir = season_directory(data_dir, year);
+    let archive = season_archive(data_dir, year);
+    let mut files = Vec::new();
+    if season_dir.is_dir() || !archive.is_file() {
+        for entry in std::fs::read_dir(&season_dir).map_err(|e| Error::io(&season_dir, e))? {
+            let path = entry.map_err(|e| Error::io(&season_dir, e))?.path();
+            files.push(SeasonFile::Extracted(path));
+        }
+    }
+    if archive.is_file() {
+        let extracted = files
+            .iter()
+            .map(|file| file.file_name().to_owned())
+            .collect::<HashSet<String>>();
+        files.extend(
+            season_file::list_archive_files(&archive)?
+                .into_iter()
+                .filter(|file| !extracted.contains(file.file_name())),
+        );
+    }
+    files.sort_by(|a, b| a.file_name().cmp(b.file_name()));
+    Ok(files)
+}
 
 #[derive(Clone)]
 pub struct Date {
@@ -13,13 +122,6 @@ impl Date {
     pub fn new(year: i32, month: i32, day: i32) -> Self {
         Self { year, month, day }
     }
-    pub fn clone(&self) -> Self {
-        Self {
-            year: self.year,
-            month: self.month,
-            day: self.day,
-        }
-    }
     pub fn get_year(&self) -> &i32 {
         &self.year
     }
@@ -31,352 +133,326 @@ impl Date {
     }
 }
 
-pub fn convert_string_to_date(date_string: &str) -> Date {
-    let date_data = date_string.split("/").collect::<Vec<&str>>();
-    let year = match date_data[0].parse::<i32>() {
-        Ok(year) => year,
-        Err(e) => panic!("Error parsing year string: {}, ({})", date_data[0], e),
-    };
-    let month = match date_data[1].parse::<i32>() {
-        Ok(month) => month,
-        Err(e) => panic!("Error parsing month string: {}, ({})", date_data[1], e),
+pub fn convert_string_to_date(date_string: &str) -> Result<Date> {
+    let invalid_date = || Error::InvalidValue {
+        value: date_string.to_owned(),
+        expected: "a yyyy/mm/dd date",
     };
-    let day = match date_data[2].parse::<i32>() {
-        Ok(day) => day,
-        Err(e) => panic!("Error parsing day string: {}, ({})", date_data[2], e),
-    };
-    Date { year, month, day }
+    let date_data = date_string
+        .split('/')
+        .map(|part| part.trim().parse::<i32>())
+        .collect::<std::result::Result<Vec<i32>, _>>()
+        .map_err(|_| invalid_date())?;
+    match date_data[..] {
+        [year, month, day] => Ok(Date { year, month, day }),
+        _ => Err(invalid_date()),
+    }
+}
+
+// How a plate appearance's pitches square with the count recorded on its final play
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PitchData {
+    // The pitches lead to the recorded count, or there is no count to check them against
+    Complete,
+    // No pitches were recorded, or some of them are unknown
+    Missing,
+    // The pitches lead to a different count than the recorded one
+    Inconsistent,
 }
 
 #[derive(Clone)]
 pub struct PlateAppearance {
     date: Date,
     batter: String,
-    outcome: char,
-    pitches: Vec<char>,
+    // Retrosheet code of the batter's team, e.g. `SFN`
+    team: String,
+    game_type: GameType,
+    // Retrosheet ID of the pitcher on the mound for the final play, when the file says
+    pitcher: Option<String>,
+    // Retrosheet ID of the home plate umpire
+    umpire: Option<String>,
+    // Side the batter hit from and the pitcher threw with, from the rosters unless the event
+    // file says otherwise
+    batter_hand: Option<Hand>,
+    pitcher_hand: Option<Hand>,
+    outcome: PlateOutcome,
+    pitches: Vec<PitchCode>,
+    // The pitch sequence as recorded, with the plays that happened during the plate appearance.
+    // Replays only change `pitches`.
+    sequence: PitchSequence,
+    // Balls and strikes before the final pitch, from the play's count field when it is known
+    count: Option<(i32, i32)>,
     raw_outcome: String,
+    // Situation when the batter came up, before any of those plays
+    game_state_at_start: GameState,
+    game_state: GameState,
+    game_state_after: GameState,
+    // Runs the batting team scored from the start of this plate appearance's final play, after
+    // any steals or wild pitches during it, to the end of the half inning. Unknown when the half
+    // inning ended without three outs (walk-offs).
+    runs_to_end_of_inning: Option<i32>,
+    // Which of the batter's plate appearances of the season this was, counting from 0, once the
+    // season is loaded
+    season_index: Option<usize>,
 }
 
 impl PlateAppearance {
     pub fn new(
         date: Date,
         batter: String,
-        outcome: char,
-        pitches: Vec<char>,
+        team: String,
+        outcome: PlateOutcome,
+        pitches: Vec<PitchCode>,
         raw_outcome: String,
+        game_state: GameState,
     ) -> Self {
         Self {
             date,
             batter,
+            team,
+            game_type: GameType::Regular,
+            pitcher: None,
+            umpire: None,
+            batter_hand: None,
+            pitcher_hand: None,
+            outcome,
+            pitches,
+            sequence: PitchSequence::default(),
+            count: None,
+            raw_outcome,
+            game_state_at_start: game_state.clone(),
+            game_state_after: game_state.clone(),
+            game_state,
+            runs_to_end_of_inning: None,
+            season_index: None,
+        }
+    }
+    // Bonds with nobody on in the first inning of a made-up game, for unit tests
+    #[cfg(test)]
+    pub(crate) fn fixture(outcome: PlateOutcome, pitches: &str) -> Self {
+        PlateAppearance::new(
+            Date::new(2004, 4, 12),
+            "bondb001".to_owned(),
+            "SFN".to_owned(),
+            outcome,
+            parse_pitch_codes(pitches),
+            outcome.to_code().to_owned(),
+            GameState::new(),
+        )
+        .with_sequence(PitchSequence::parse(pitches))
+    }
+    // The situation once the plate appearance's final play is over
+    pub fn with_game_state_after(mut self, game_state_after: GameState) -> Self {
+        self.game_state_after = game_state_after;
+        self
+    }
+    pub fn with_sequence(mut self, sequence: PitchSequence) -> Self {
+        self.sequence = sequence;
+        self
+    }
+    pub fn with_count(mut self, balls: i32, strikes: i32) -> Self {
+        self.count = Some((balls, strikes));
+        self
+    }
+    pub fn with_game_state_at_start(mut self, game_state_at_start: GameState) -> Self {
+        self.game_state_at_start = game_state_at_start;
+        self
+    }
+    pub fn with_game_type(mut self, game_type: GameType) -> Self {
+        self.game_type = game_type;
+        self
+    }
+    pub fn with_pitcher(mut self, pitcher: String) -> Self {
+        self.pitcher = Some(pitcher);
+        self
+    }
+    pub fn with_umpire(mut self, umpire: String) -> Self {
+        self.umpire = Some(umpire);
+        self
+    }
+    pub fn with_batter_hand(mut self, hand: Hand) -> Self {
+        self.batter_hand = Some(hand);
+        self
+    }
+    pub fn with_pitcher_hand(mut self, hand: Hand) -> Self {
+        self.pitcher_hand = Some(hand);
+        self
+    }
+    // The same plate appearance ending another way, as replayed by a simulation
+    pub(crate) fn replayed(
+        &self,
+        outcome: PlateOutcome,
+        pitches: Vec<PitchCode>,
+        raw_outcome: String,
+        game_state_after: GameState,
+    ) -> Self {
+        Self {
             outcome,
             pitches,
             raw_outcome,
+            game_state_after,
+            ..self.clone()
         }
     }
+    // Fill in hands the event file left out
+    pub(crate) fn set_missing_hands(&mut self, batter: Option<Hand>, pitcher: Option<Hand>) {
+        self.batter_hand = self.batter_hand.or(batter);
+        self.pitcher_hand = self.pitcher_hand.or(pitcher);
+    }
+    pub(crate) fn set_runs_to_end_of_inning(&mut self, runs: Option<i32>) {
+        self.runs_to_end_of_inning = runs;
+    }
+    pub(crate) fn set_season_index(&mut self, index: usize) {
+        self.season_index = Some(index);
+    }
     pub fn get_date(&self) -> &Date {
         &self.date
     }
     pub fn get_batter(&self) -> &String {
         &self.batter
     }
-    pub fn get_outcome(&self) -> &char {
+    pub fn get_team(&self) -> &String {
+        &self.team
+    }
+    pub fn get_game_type(&self) -> &GameType {
+        &self.game_type
+    }
+    pub fn get_pitcher(&self) -> &Option<String> {
+        &self.pitcher
+    }
+    pub fn get_umpire(&self) -> &Option<String> {
+        &self.umpire
+    }
+    pub fn get_batter_hand(&self) -> &Option<Hand> {
+        &self.batter_hand
+    }
+    pub fn get_pitcher_hand(&self) -> &Option<Hand> {
+        &self.pitcher_hand
+    }
+    pub fn get_outcome(&self) -> &PlateOutcome {
         &self.outcome
     }
-    pub fn get_pitches(&self) -> &Vec<char> {
+    pub fn get_pitches(&self) -> &Vec<PitchCode> {
         &self.pitches
     }
+    pub fn get_sequence(&self) -> &PitchSequence {
+        &self.sequence
+    }
+    pub fn get_count(&self) -> &Option<(i32, i32)> {
+        &self.count
+    }
+    pub fn pitch_data(&self) -> PitchData {
+        let pitches = self
+            .pitches
+            .iter()
+            .filter(|pitch| pitch.is_pitch())
+            .collect::<Vec<&PitchCode>>();
+        if pitches.is_empty() || pitches.contains(&&PitchCode::Unknown) {
+            return PitchData::Missing;
+        }
+        let (mut balls, mut strikes) = (0, 0);
+        for pitch in &pitches[..pitches.len() - 1] {
+            if codes::apply_pitch(pitch, &mut balls, &mut strikes) {
+                return PitchData::Inconsistent;
+            }
+        }
+        match self.count {
+            Some(count) if count != (balls, strikes) => PitchData::Inconsistent,
+            _ => PitchData::Complete,
+        }
+    }
+    // Count a replay starts from instead of going through the pitches, when they are missing or
+    // contradict the recorded count and the count is known
+    pub fn fallback_count(&self) -> Option<(i32, i32)> {
+        match self.pitch_data() {
+            PitchData::Complete => None,
+            PitchData::Missing | PitchData::Inconsistent => self.count,
+        }
+    }
     pub fn get_raw_outcome(&self) -> &String {
         &self.raw_outcome
     }
+    pub fn get_game_state_at_start(&self) -> &GameState {
+        &self.game_state_at_start
+    }
+    // Inning, outs, baserunners and score going into the play that ended the plate appearance
+    pub fn get_game_state(&self) -> &GameState {
+        &self.game_state
+    }
+    pub fn get_game_state_after(&self) -> &GameState {
+        &self.game_state_after
+    }
+    // Runs that scored on the plate appearance's final play
+    pub fn runs_on_play(&self) -> i32 {
+        self.game_state_after.get_batting_team_score() - self.game_state.get_batting_team_score()
+    }
+    pub fn get_runs_to_end_of_inning(&self) -> &Option<i32> {
+        &self.runs_to_end_of_inning
+    }
+    pub fn get_season_index(&self) -> &Option<usize> {
+        &self.season_index
+    }
 }
 
 impl fmt::Display for PlateAppearance {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         write!(
             f,
-            "Date: {}/{}/{}, Pitches: {:?}, Outcome: {}, debug: {:?}",
+            "Date: {}/{}/{}, {}, Pitches: {}, Recorded: {}, Outcome: {}, debug: {:?}",
             self.date.get_year(),
             self.date.get_month(),
             self.date.get_day(),
-            self.pitches,
+            self.game_state,
+            self.pitches
+                .iter()
+                .map(PitchCode::to_char)
+                .collect::<String>(),
+            self.sequence,
             self.outcome,
             self.raw_outcome,
         )
     }
 }
 
-pub fn is_ball(pitch: &char) -> bool {
-    match pitch {
-        'B' => true, // ball
-        'I' => true, // intentional ball
-        'P' => true, // pitchouts are balls
-        'V' => true, // called ball because pitcher went to his mouth
-        _ => false,  // unknown
-    }
+pub fn sum_walks(plate_appearances: &[PlateAppearance]) -> i32 {
+    *BattingLine::from_plate_appearances(plate_appearances).get_walks() as i32
 }
 
-pub fn is_strike(pitch: &char) -> bool {
-    match pitch {
-        'A' => true, // automatic strike
-        'C' => true, // called strike
-        'M' => true, // missed bunt attempt, need re-sim without bat
-        'Q' => true, // swinging on pitchout
-        'S' => true, // swinging strike, need re-sim without bat so we'll call it a foul ball
-        'T' => true, // foul tip, need re-sim without bat
-        _ => false,  // unknown
-    }
+pub fn sum_strikeouts(plate_appearances: &[PlateAppearance]) -> i32 {
+    *BattingLine::from_plate_appearances(plate_appearances).get_strikeouts() as i32
 }
 
-pub fn is_foul(pitch: &char) -> bool {
-    match pitch {
-        'F' => true, // foul ball, need re-sim without bat
-        'L' => true, // foul bunt, need re-sim without bat
-        'O' => true, // foul tip on bunt, need re-sim without bat
-        'R' => true, // foul ball on pitchout, pitchouts are balls
-        _ => false,  // unknown
-    }
+// Official on-base percentage of the plate appearances
+pub fn calculate_obp(plate_appearances: &[PlateAppearance]) -> f32 {
+    BattingLine::from_plate_appearances(plate_appearances).on_base_percentage()
 }
 
-pub fn is_ball_put_into_play_or_hit_by_pitch(pitch: &char) -> bool {
-    match pitch {
-        'H' => true, // hit by pitch
-        'X' => true, // ball put into play, need re-sim without bat
-        'Y' => true, // ball put into play on pitchout, pitchouts are balls
-        _ => false,  // unknown
-    }
+// Official on-base percentage worked out from the Retrosheet event strings rather than the
+// outcomes, so simulated plate appearances count as what actually happened
+pub fn calculate_obp_from_raw_outcomes(plate_appearances: &[PlateAppearance]) -> f32 {
+    let outcomes = plate_appearances
+        .iter()
+        .filter_map(|appearance| PlateOutcome::from_event(appearance.get_raw_outcome()))
+        .collect::<Vec<PlateOutcome>>();
+    BattingLine::from_outcomes(&outcomes).on_base_percentage()
 }
 
-// Create some integer mappings for the different pitch results
-pub fn simplify_pitch_codes(pitch: &char) -> char {
-    match pitch {
-        'A' => 'C', // automatic strike
-        'B' => 'B', // ball
-        'C' => 'C', // called strike
-        'F' => 'F', // foul ball, need re-sim without bat
-        'H' => 'H', // hit by pitch
-        'I' => 'I', // intentional ball
-        'L' => 'F', // foul bunt, need re-sim without bat
-        'M' => 'F', // missed bunt attempt, need re-sim without bat
-        'N' => 'N', // no pitch (on balks and interference calls), not sure what to do
-        'O' => 'F', // foul tip on bunt, need re-sim without bat
-        'P' => 'B', // pitchouts are balls
-        'Q' => 'B', // swinging on pitchout
-        'R' => 'B', // foul ball on pitchout, pitchouts are balls
-        'S' => 'F', // swinging strike, need re-sim without bat so we'll call it a foul ball
-        'T' => 'F', // foul tip, need re-sim without bat
-        'U' => 'N', // unknown or missed pitch
-        'V' => 'B', // called ball because pitcher went to his mouth
-        'X' => 'X', // ball put into play, need re-sim without bat
-        'Y' => 'B', // ball put into play on pitchout, pitchouts are balls
-        _ => 'N',   // unknown
-    }
-}
-
-pub fn simplify_outcome_codes(last_pitch: &char, outcome: &char) -> char {
-    let new_outcome = match outcome {
-        // Look these up here https://www.retrosheet.org/eventfile.htm#5
-        'S' => 'S', // single
-        'D' => 'D', // double
-        'T' => 'T', // triple
-        'H' => 'H', // home run
-        'W' => 'W', // walk
-        'I' => 'I', // intentional walk
-        'E' => 'E', // error
-        'F' => 'O', // foul out
-        'K' => 'K', // strikeout
-        '0' => 'O', // fielded out
-        '1' => 'O', // fielded out
-        '2' => 'O', // fielded out
-        '3' => 'O', // fielded out
-        '4' => 'O', // fielded out
-        '5' => 'O', // fielded out
-        '6' => 'O', // fielded out
-        '7' => 'O', // fielded out
-        '8' => 'O', // fielded out
-        '9' => 'O', // fielded out
-        _ => 'N',   // unknown, ignore
-    };
-    if last_pitch == &'H' {
-        return 'W';
-    } else {
-        return new_outcome;
-    }
-}
-
-pub fn sum_walks(plate_appearances: &Vec<PlateAppearance>) -> i32 {
-    let mut walks = 0;
-    for plate_appearance in plate_appearances {
-        if plate_appearance.get_outcome() == &'W' {
-            walks += 1;
-        }
-    }
-    return walks;
-}
-
-pub fn sum_strikeouts(plate_appearances: &Vec<PlateAppearance>) -> i32 {
-    let mut strikeouts = 0;
-    for plate_appearance in plate_appearances {
-        if plate_appearance.get_outcome() == &'K' {
-            strikeouts += 1;
-        }
-    }
-    return strikeouts;
-}
-
-pub fn calculate_obp(plate_appearances: &Vec<PlateAppearance>) -> f32 {
-    let mut hits = 0.0;
-    let mut walks = 0.0;
-    let mut at_bats = 0.0;
-    for plate_appearance in plate_appearances {
-        let outcome = plate_appearance.get_outcome();
-        if outcome == &'S' || outcome == &'D' || outcome == &'T' || outcome == &'H' {
-            // singles, doubles, triples, and home runs
-            hits += 1.0;
-            at_bats += 1.0;
-        }
-        if outcome == &'W' || outcome == &'I' {
-            // walks and intentional walks
-            walks += 1.0;
-            at_bats += 1.0;
-        }
-        if outcome == &'O' || outcome == &'K' || outcome == &'E' {
-            // fielded out or strikeout
-            at_bats += 1.0;
-        }
-    }
-    return (hits + walks) / at_bats;
-}
-
-fn is_error(outcome: &String) -> bool {
-    let id_found = outcome.find("E");
-    if id_found.is_none() {
-        return false;
-    } else {
-        if outcome
-            .chars()
-            .nth(id_found.unwrap() + 1)
-            .unwrap()
-            .is_digit(10)
-        {
-            return true;
-        }
-        return false;
-    }
-}
-
-pub fn calculate_obp_from_raw_outcomes(plate_appearances: &Vec<PlateAppearance>) -> f32 {
-    let mut hits = 0.0;
-    let mut walks = 0.0;
-    let mut at_bats = 0.0;
-    for plate_appearance in plate_appearances {
-        let outcome_string = plate_appearance.get_raw_outcome();
-        let outcome = outcome_string.chars().nth(0).unwrap();
-        if outcome == 'S' || outcome == 'D' || outcome == 'T' || outcome == 'H' {
-            // singles, doubles, triples, and home runs
-            hits += 1.0;
-            at_bats += 1.0;
-        }
-        if outcome == 'W' || outcome == 'I' {
-            // walks and intentional walks
-            walks += 1.0;
-            at_bats += 1.0;
-        }
-        if outcome.is_digit(10) || outcome == 'K' || is_error(&outcome_string) {
-            // fielded out or strikeout
-            at_bats += 1.0;
-        }
-    }
-    return (hits + walks) / at_bats;
-}
-
-fn simulate_until_outcome(zone_pct: f32, mut balls: i32, mut strikes: i32) -> (char, Vec<char>) {
-    let mut simulated_pitches: Vec<char> = Vec::new();
-    while balls < 4 && strikes < 3 {
-        let zone = rand::thread_rng().gen_range(0.0..100.0);
-        if zone <= zone_pct {
-            simulated_pitches.push('C');
-            strikes += 1;
-        } else {
-            simulated_pitches.push('B');
-            balls += 1;
-        }
-    }
-    if balls == 4 {
-        return ('W', simulated_pitches);
-    } else {
-        return ('K', simulated_pitches);
-    }
-}
-
-pub fn simulate_plate_appearance_no_bat(
+// Replay a plate appearance as if the batter never swung. Randomness comes from `rng` so a seeded
+// generator reproduces the same simulation.
+pub fn simulate_plate_appearance_no_bat<R: Rng + ?Sized>(
+    rng: &mut R,
     appearance: &PlateAppearance,
     oswing_pct: f32,
     swing_pct: f32,
-    zone_pct: f32,
+    zone_model: &ZoneModel,
 ) -> PlateAppearance {
-    let mut balls = 0;
-    let mut strikes = 0;
-    let mut pitches_no_bat: Vec<char> = Vec::new();
-    // In theses cases, we need the probability that the thrown pitch was outside the strike zone
-    // given that the player swung.
-    // p(outside zone | swing) = p(swing | outside zone) * p(outside zone) / p(swing) - or -
-    // p(outside zone | swing) = (OSwing% * (1 - Zone%)) / Swing%
-    let prob_ball_given_swing = (oswing_pct * (100.0 - zone_pct)) / swing_pct;
-    for pitch in &appearance.pitches {
-        // B, balls stay balls,
-        // C, strikes with no swing stay strikes,
-        // I, intentional walks stay intentional walks
-        // H, hit by pitches stay hit by pitches
-        // F, foul balls require us to re-simulate the pitch without the bat
-        // X, ball put into play requires us to re-simulate the pitch with no bat
-        // if we still have no outcome by the end, we need to simulate until we get one
-        let pitch = pitch.to_owned();
-        
-        if pitch == 'B' || pitch == 'C' || pitch == 'I' || pitch == 'H' {
-            pitches_no_bat.push(pitch);
-            if pitch == 'B' || pitch == 'I' {
-                balls += 1;
-            } else if pitch == 'C' {
-                strikes += 1;
-            } else if pitch == 'H' {
-                balls = 4;
-            }
-        } else if pitch == 'F' || pitch == 'X' {
-            // Batter swung at the pitch, so we need to re-simulate the pitch without the bat
-            // Probability that it was inside the strike zone
-            let zone = rand::thread_rng().gen_range(0.0..100.0);
-            if zone <= prob_ball_given_swing {
-                pitches_no_bat.push('B');
-                balls += 1;
-            } else {
-                pitches_no_bat.push('C');
-                strikes += 1;
-            }
-        }
-        if balls == 4 || strikes == 3 {
-            break;
-        }
-    }
-
-    let outcome_no_bat;
-    if balls == 4 {
-        outcome_no_bat = 'W';
-    } else if strikes == 3 {
-        outcome_no_bat = 'K';
-    } else {
-        let (simulated_outcome, simulated_pitches) =
-            simulate_until_outcome(zone_pct, balls, strikes);
-        outcome_no_bat = simulated_outcome;
-        for pitch in simulated_pitches {
-            pitches_no_bat.push(pitch);
-        }
-    }
-    let simulated_appearance = PlateAppearance::new(
-        appearance.date.clone(),
-        appearance.batter.clone(),
-        outcome_no_bat,
-        pitches_no_bat,
-        appearance.raw_outcome.clone(),
-    );
-    return simulated_appearance;
+    strategy::simulate_plate_appearance_with_strategy(
+        rng,
+        appearance,
+        &SwingStrategy::Never,
+        &SwingPool::default(),
+        oswing_pct,
+        swing_pct,
+        zone_model,
+    )
 }
@@ -0,0 +1,246 @@
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+
+use crate::discipline::{normalize_header, split_fields};
+use crate::error::{Error, Result};
+use crate::markov::ExpectedNoBatSeason;
+use crate::stat_line::BattingLine;
+
+// FanGraphs "Guts!" table saved in the data directory, see
+// https://www.fangraphs.com/guts.aspx?type=cn
+pub static GUTS_FILE: &str = "guts.csv";
+
+// One season's wOBA weights and league context
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct LinearWeights {
+    year: i32,
+    league_woba: f32,
+    woba_scale: f32,
+    walk: f32,
+    hit_by_pitch: f32,
+    single: f32,
+    double: f32,
+    triple: f32,
+    home_run: f32,
+}
+
+impl LinearWeights {
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        year: i32,
+        league_woba: f32,
+        woba_scale: f32,
+        walk: f32,
+        hit_by_pitch: f32,
+        single: f32,
+        double: f32,
+        triple: f32,
+        home_run: f32,
+    ) -> Self {
+        Self {
+            year,
+            league_woba,
+            woba_scale,
+            walk,
+            hit_by_pitch,
+            single,
+            double,
+            triple,
+            home_run,
+        }
+    }
+    pub fn get_year(&self) -> &i32 {
+        &self.year
+    }
+    pub fn get_league_woba(&self) -> &f32 {
+        &self.league_woba
+    }
+    pub fn get_woba_scale(&self) -> &f32 {
+        &self.woba_scale
+    }
+    pub fn get_walk(&self) -> &f32 {
+        &self.walk
+    }
+    pub fn get_hit_by_pitch(&self) -> &f32 {
+        &self.hit_by_pitch
+    }
+    pub fn get_single(&self) -> &f32 {
+        &self.single
+    }
+    pub fn get_double(&self) -> &f32 {
+        &self.double
+    }
+    pub fn get_triple(&self) -> &f32 {
+        &self.triple
+    }
+    pub fn get_home_run(&self) -> &f32 {
+        &self.home_run
+    }
+
+    // (wBB*uBB + wHBP*HBP + w1B*1B + w2B*2B + w3B*3B + wHR*HR) / (AB + BB - IBB + SF + HBP)
+    pub fn woba(&self, line: &BattingLine) -> f32 {
+        let unintentional_walks = line.get_walks() - line.get_intentional_walks();
+        let numerator = self.walk * unintentional_walks as f32
+            + self.hit_by_pitch * *line.get_hit_by_pitch() as f32
+            + self.single * *line.get_singles() as f32
+            + self.double * *line.get_doubles() as f32
+            + self.triple * *line.get_triples() as f32
+            + self.home_run * *line.get_home_runs() as f32;
+        let denominator = line.get_at_bats()
+            + unintentional_walks
+            + line.get_sacrifice_flies()
+            + line.get_hit_by_pitch();
+        numerator / denominator as f32
+    }
+
+    // Runs above an average hitter over the same number of plate appearances (wRAA)
+    pub fn batting_runs(&self, woba: f32, plate_appearances: f32) -> f32 {
+        (woba - self.league_woba) / self.woba_scale * plate_appearances
+    }
+
+    pub fn batting_runs_for_line(&self, line: &BattingLine) -> f32 {
+        self.batting_runs(self.woba(line), *line.get_plate_appearances() as f32)
+    }
+
+    // Expected wOBA of an exactly computed no-bat season. Without a bat the only ways on are
+    // walks and hit batters, every at bat is a strikeout and there are no sacrifice flies.
+    pub fn expected_woba_no_bat(&self, expected: &ExpectedNoBatSeason) -> f32 {
+        let unintentional_walks =
+            expected.get_expected_walks() - expected.get_expected_intentional_walks();
+        let numerator = self.walk as f64 * unintentional_walks
+            + self.hit_by_pitch as f64 * expected.get_expected_hit_by_pitch();
+        let denominator =
+            *expected.get_plate_appearances() as f64 - expected.get_expected_intentional_walks();
+        (numerator / denominator) as f32
+    }
+}
+
+// Parse a guts table with at least the Season, wOBA, wOBAScale, wBB, wHBP, w1B, w2B, w3B and
+// wHR columns, keyed by season
+pub fn parse_linear_weights(contents: &str, file: &str) -> Result<HashMap<i32, LinearWeights>> {
+    let mut lines = contents
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty());
+    let header = match lines.next() {
+        Some((_, line)) => split_fields(line.trim_start_matches('\u{feff}'), ',')
+            .iter()
+            .map(|field| normalize_header(field))
+            .collect::<Vec<String>>(),
+        None => return Ok(HashMap::new()),
+    };
+    let column = |name: &str| {
+        header
+            .iter()
+            .position(|field| field == name)
+            .ok_or_else(|| Error::malformed(file, 1, format!("guts table has no {} column", name)))
+    };
+    let columns = [
+        column("season")?,
+        column("woba")?,
+        column("wobascale")?,
+        column("wbb")?,
+        column("whbp")?,
+        column("w1b")?,
+        column("w2b")?,
+        column("w3b")?,
+        column("whr")?,
+    ];
+
+    let mut weights: HashMap<i32, LinearWeights> = HashMap::new();
+    for (index, line) in lines {
+        let fields = split_fields(line.trim_end(), ',');
+        let mut values = [0.0; 9];
+        for (value, column) in values.iter_mut().zip(columns) {
+            let field = fields.get(column).map(|field| field.as_str()).unwrap_or("");
+            *value = field
+                .parse::<f32>()
+                .map_err(|_| Error::malformed(file, index + 1, format!("bad value {:?}", field)))?;
+        }
+        let year = values[0] as i32;
+        weights.insert(
+            year,
+            LinearWeights::new(
+                year, values[1], values[2], values[3], values[4], values[5], values[6], values[7],
+                values[8],
+            ),
+        );
+    }
+    Ok(weights)
+}
+
+// Load the season's weights from `<data_dir>/guts.csv`
+pub fn read_linear_weights(data_dir: &Path, year: i32) -> Result<LinearWeights> {
+    let path_in = data_dir.join(GUTS_FILE);
+    let contents = fs::read_to_string(&path_in).map_err(|e| Error::io(&path_in, e))?;
+    parse_linear_weights(&contents, &path_in.display().to_string())?
+        .remove(&year)
+        .ok_or(Error::MissingLinearWeights { year })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::PlateOutcome;
+
+    // Made-up weights with round numbers
+    fn weights() -> LinearWeights {
+        LinearWeights::new(2004, 0.330, 1.2, 0.7, 0.72, 0.9, 1.25, 1.6, 2.0)
+    }
+
+    #[test]
+    fn woba_of_a_hand_computed_line() {
+        use PlateOutcome::*;
+        let line = BattingLine::from_outcomes(&[
+            Single,
+            Double,
+            HomeRun,
+            Strikeout,
+            Walk,
+            IntentionalWalk,
+            HitByPitch,
+            SacrificeFly,
+            SacrificeBunt,
+        ]);
+        // 0.7 + 0.72 + 0.9 + 1.25 + 2.0 over 4 AB + 1 uBB + 1 SF + 1 HBP, the intentional walk
+        // and the bunt left out
+        let woba = weights().woba(&line);
+        assert!((woba - 5.57 / 7.0).abs() < 1e-6, "{}", woba);
+        // Nine plate appearances at that wOBA against a .330 league
+        let runs = weights().batting_runs_for_line(&line);
+        assert!(
+            (runs - (5.57 / 7.0 - 0.330) / 1.2 * 9.0).abs() < 1e-5,
+            "{}",
+            runs
+        );
+    }
+
+    #[test]
+    fn parses_guts_tables_by_header() {
+        let contents = "\u{feff}\"Season\",\"lg_r_pa\",\"wHR\",\"wOBA\",\"wBB\",\"wHBP\",\
+                        \"w1B\",\"w2B\",\"w3B\",\"wOBAScale\"\n\
+                        2004,0.121,2.031,0.333,0.719,0.750,0.898,1.270,1.608,1.215\n\
+                        \n\
+                        2022,0.114,2.072,0.310,0.689,0.720,0.884,1.261,1.601,1.259\n";
+        let weights = parse_linear_weights(contents, "guts.csv").unwrap();
+        assert_eq!(weights.len(), 2);
+        assert_eq!(
+            weights[&2004],
+            LinearWeights::new(2004, 0.333, 1.215, 0.719, 0.750, 0.898, 1.270, 1.608, 2.031)
+        );
+        assert_eq!(
+            weights[&2022],
+            LinearWeights::new(2022, 0.310, 1.259, 0.689, 0.720, 0.884, 1.261, 1.601, 2.072)
+        );
+    }
+
+    #[test]
+    fn guts_table_missing_a_column_is_an_error() {
+        let contents = "Season,wOBA,wOBAScale,wBB,wHBP,w1B,w2B,w3B\n2004,0.333,1.215,0.719,0.750,0.898,1.270,1.608\n";
+        assert!(matches!(
+            parse_linear_weights(contents, "guts.csv"),
+            Err(Error::MalformedRecord { line: 1, .. })
+        ));
+    }
+}
@@ -1,289 +1,1214 @@
+use no_bat_simulator::markov::{expected_season_no_bat, ExpectedNoBatSeason};
+use no_bat_simulator::monte_carlo::{
+    replicate_season, replicate_season_no_bat, ReplicationSummary,
+};
+use no_bat_simulator::strategy::simulate_season_with_strategy;
 use no_bat_simulator::{
-    calculate_obp, convert_string_to_date, is_ball, is_ball_put_into_play_or_hit_by_pitch, is_foul,
-    is_strike, simplify_outcome_codes, simplify_pitch_codes, simulate_plate_appearance_no_bat,
-    Date,
+    calculate_obp, list_season_years, normalize_name, read_linear_weights, read_season_rosters,
+    BattingLine, Error, GameSelection, Hand, LinearWeights, PitchData, PitcherAdaptation,
+    PitcherZoneSource, PlateAppearance, PlateDiscipline, PlatoonSplits, PlayerId, Season,
+    SwingPool, SwingStrategy, ZoneModel,
 };
-use std::fs;
+use rand::SeedableRng;
+use rand_chacha::ChaCha8Rng;
+use std::collections::HashSet;
 use std::path::Path;
 
-use no_bat_simulator::PlateAppearance;
-
 static DATA_DIR: &str = "data";
 
-fn split_player_name_into_first_and_last(player_name: &String) -> (String, String) {
-    let player_name_split = player_name.split(" ").collect::<Vec<&str>>();
-    let player_firstname = player_name_split[0].to_owned();
-    let player_lastname = player_name_split[1..].join(" ");
-    (player_firstname, player_lastname)
+static USAGE: &str = "Usage: no_bat_simulator <player name> [team] <year>|<first>-<last>|career \
+                      | all <year> \
+                      [--replications N] [--seed N] [--exact] [--rank obp|runs] \
+                      [--count-zones] [--zone-blend W] \
+                      [--adapt-after N] [--adapt-over N] [--adapt-zone PCT] [--strategies] \
+                      [--pitcher-zones csv|taken] [--pitcher-blend W] \
+                      [--umpires] [--umpire-range] [--platoon] [--show-pitches] \
+                      [--games regular|postseason|allstar|all]";
+
+// How the leaderboard is ordered
+#[derive(Clone, Copy, PartialEq)]
+enum Rank {
+    // OBP without a bat
+    Obp,
+    // Batting runs gained by never swinging, needs the guts table
+    Runs,
 }
 
-fn get_player_id_from_file(player_name: &String, team_name: &String, year: i32) -> String {
-    let (player_firstname, player_lastname) = split_player_name_into_first_and_last(player_name);
-
-    // Open the .ROS file for the player's team and find the player's ID
-    let path_text = format!("{DATA_DIR}/{}eve/{team_name}{}.ROS", year, year);
-    let path_in_roster = Path::new(&path_text);
-    let contents_roster =
-        fs::read_to_string(path_in_roster).expect("Something went wrong reading the file");
-    let lines = contents_roster.split("\n");
-    for line in lines {
-        // Check if the line contains the player's name
-        let line_data = line.split(",").collect::<Vec<&str>>();
-        if line_data.len() < 3 {
-            continue;
-        }
-        if line_data[1] == &player_lastname && line_data[2] == &player_firstname {
-            // If so, return the player's ID
-            return line_data[0].to_owned();
+// Which seasons to replay
+#[derive(Clone, Copy, PartialEq)]
+enum Seasons {
+    Year(i32),
+    // First and last year, inclusive
+    Range(i32, i32),
+    // Every season directory the player shows up in
+    Career,
+}
+
+struct Options {
+    player_name: String,
+    // Only needed to tell apart two players with the same name
+    team_name: Option<String>,
+    seasons: Seasons,
+    // How many times to replay the season without a bat
+    replications: usize,
+    // Seed for the random number generator, fresh entropy when not given
+    seed: Option<u64>,
+    // Compute no-bat probabilities exactly instead of sampling
+    exact: bool,
+    rank: Rank,
+    // Call taken pitches with the league's strike rate for each count instead of Zone%
+    count_zones: bool,
+    // How far the rates by count go from the league's average Zone% to the batter's own, 0 to 1
+    zone_blend: f32,
+    // Plate appearances before pitchers start pounding the zone, no adjustment when not given
+    adapt_after: Option<usize>,
+    // Plate appearances it takes them to get all the way there
+    adapt_over: usize,
+    // Zone% they settle on, capped at the best control in the league
+    adapt_zone: Option<f32>,
+    // Replay a single player's season under every selective swing strategy
+    strategies: bool,
+    // Use each pitcher's Zone% too, from the pitcher table or the pitches taken against him
+    pitcher_zones: Option<PitcherZoneSource>,
+    // Share of the pitcher's Zone% mixed into the batter's, 0 to 1
+    pitcher_blend: f32,
+    // Call taken pitches the way each game's home plate umpire did
+    umpires: bool,
+    // Also replay the season in front of an umpire with a generous and with a tight zone
+    umpire_range: bool,
+    // Use the batter's rates against the hand of the pitcher he is facing
+    platoon: bool,
+    // List a single player's plate appearances pitch by pitch
+    show_pitches: bool,
+    // Regular season, postseason or All-Star games, or all of them
+    games: GameSelection,
+}
+
+fn parse_year(year: &str) -> Result<i32, String> {
+    year.parse::<i32>()
+        .map_err(|_| format!("{:?} is not a year\n{}", year, USAGE))
+}
+
+// `2004`, `2000-2007` or `career`
+fn parse_seasons(seasons: &str) -> Result<Seasons, String> {
+    if seasons == "career" {
+        return Ok(Seasons::Career);
+    }
+    match seasons.split_once('-') {
+        Some((first, last)) => {
+            let (first, last) = (parse_year(first)?, parse_year(last)?);
+            if first > last {
+                return Err(format!("{:?} is not a range of years\n{}", seasons, USAGE));
+            }
+            Ok(Seasons::Range(first, last))
         }
+        None => Ok(Seasons::Year(parse_year(seasons)?)),
     }
-    // If the player's name was not found in the roster, return an empty string
-    let blank_string = "";
-    return blank_string.to_owned();
 }
 
-fn read_plate_appearances_from_file(
-    player_id: &String,
-    player_name: &String,
-    path: &Path,
-) -> Vec<PlateAppearance> {
-    let contents = fs::read_to_string(path).expect("Something went wrong reading Retrosheet files");
-    // Split data by newline
-    let lines = contents.split("\n");
-    let mut last_game_date = Date::new(0, 0, 0);
-    let mut plate_appearances: Vec<PlateAppearance> = Vec::new();
-    for line in lines {
-        // Remove trailing newline character
-        let mut line_trimmed = line.to_string();
-        line_trimmed.pop();
-        let line_data = line_trimmed.split(",").collect::<Vec<&str>>();
-        if line_data[0] == "info" && line_data[1] == "date" {
-            last_game_date = convert_string_to_date(line_data[2]);
-        }
-        // Check if the line contains the player's ID
-        if line_data[0] == "play" && line_data[3] == player_id {
-            // If so, create a plate appearance from the line and add it to the vector
-            let pitches_complex = line_data[5].chars().collect::<Vec<char>>();
-            let mut pitches: Vec<char> = Vec::new();
-            let mut balls = 0;
-            let mut strikes = 0;
-            let mut outcome = 'N';
-            for pitch in &pitches_complex {
-                let pitch_simple: char = simplify_pitch_codes(pitch);
-                if pitch_simple == 'N' {
-                    continue;
-                }
-                pitches.push(pitch_simple);
-                if is_ball(pitch) {
-                    balls += 1;
-                } else if is_strike(pitch) {
-                    strikes += 1;
-                } else if is_foul(pitch) {
-                    strikes = std::cmp::min(strikes + 1, 2);
-                } else if is_ball_put_into_play_or_hit_by_pitch(pitch) {
-                    // If the pitch was put into play or hit the batter, the plate appearance is over
-                    let outcome_complex = line_data[6].chars().collect::<Vec<char>>()[0];
-                    outcome = simplify_outcome_codes(pitch, &outcome_complex);
-                }
+fn parse_input_arguments(args: Vec<String>) -> Result<Options, String> {
+    let mut positional: Vec<String> = Vec::new();
+    let mut replications = 1;
+    let mut seed = None;
+    let mut exact = false;
+    let mut rank = Rank::Obp;
+    let mut count_zones = false;
+    let mut zone_blend = 1.0;
+    let mut adapt_after = None;
+    let mut adapt_over = 100;
+    let mut adapt_zone = None;
+    let mut strategies = false;
+    let mut pitcher_zones = None;
+    let mut pitcher_blend = 0.5;
+    let mut umpires = false;
+    let mut umpire_range = false;
+    let mut platoon = false;
+    let mut show_pitches = false;
+    let mut games = GameSelection::Regular;
+    let mut args = args.into_iter().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--replications" | "-n" => {
+                let value = args.next().unwrap_or_default();
+                replications = match value.parse::<usize>() {
+                    Ok(replications) if replications > 0 => replications,
+                    _ => {
+                        return Err(format!(
+                            "{:?} is not a number of replications\n{}",
+                            value, USAGE
+                        ))
+                    }
+                };
             }
-
-            // Parse the outcome of the plate appearance
-            if outcome == 'N' {
-                if balls == 4 {
-                    outcome = 'W';
-                } else if strikes == 3 {
-                    outcome = 'K';
-                }
+            "--seed" => {
+                let value = args.next().unwrap_or_default();
+                seed = match value.parse::<u64>() {
+                    Ok(seed) => Some(seed),
+                    Err(_) => return Err(format!("{:?} is not a seed\n{}", value, USAGE)),
+                };
+            }
+            "--exact" => exact = true,
+            "--rank" => {
+                let value = args.next().unwrap_or_default();
+                rank = match value.as_str() {
+                    "obp" => Rank::Obp,
+                    "runs" => Rank::Runs,
+                    _ => return Err(format!("{:?} is not a ranking\n{}", value, USAGE)),
+                };
+            }
+            "--count-zones" => count_zones = true,
+            "--zone-blend" => {
+                let value = args.next().unwrap_or_default();
+                zone_blend = match value.parse::<f32>() {
+                    Ok(weight) if (0.0..=1.0).contains(&weight) => weight,
+                    _ => {
+                        return Err(format!(
+                            "{:?} is not a blend weight between 0 and 1\n{}",
+                            value, USAGE
+                        ))
+                    }
+                };
+            }
+            "--adapt-after" => {
+                let value = args.next().unwrap_or_default();
+                adapt_after = match value.parse::<usize>() {
+                    Ok(after) => Some(after),
+                    Err(_) => {
+                        return Err(format!(
+                            "{:?} is not a number of plate appearances\n{}",
+                            value, USAGE
+                        ))
+                    }
+                };
+            }
+            "--adapt-over" => {
+                let value = args.next().unwrap_or_default();
+                adapt_over = match value.parse::<usize>() {
+                    Ok(over) => over,
+                    Err(_) => {
+                        return Err(format!(
+                            "{:?} is not a number of plate appearances\n{}",
+                            value, USAGE
+                        ))
+                    }
+                };
             }
+            "--adapt-zone" => {
+                let value = args.next().unwrap_or_default();
+                adapt_zone = match value.parse::<f32>() {
+                    Ok(pct) if (0.0..=100.0).contains(&pct) => Some(pct),
+                    _ => {
+                        return Err(format!(
+                            "{:?} is not a Zone% between 0 and 100\n{}",
+                            value, USAGE
+                        ))
+                    }
+                };
+            }
+            "--strategies" => strategies = true,
+            "--umpires" => umpires = true,
+            "--umpire-range" => umpire_range = true,
+            "--platoon" => platoon = true,
+            "--show-pitches" => show_pitches = true,
+            "--pitcher-zones" => {
+                let value = args.next().unwrap_or_default();
+                pitcher_zones = match value.as_str() {
+                    "csv" => Some(PitcherZoneSource::Discipline),
+                    "taken" => Some(PitcherZoneSource::TakenPitches),
+                    _ => {
+                        return Err(format!(
+                            "{:?} is not a source of pitcher zone rates\n{}",
+                            value, USAGE
+                        ))
+                    }
+                };
+            }
+            "--games" => {
+                let value = args.next().unwrap_or_default();
+                games = match GameSelection::from_name(&value) {
+                    Some(games) => games,
+                    None => return Err(format!("{:?} is not a kind of game\n{}", value, USAGE)),
+                };
+            }
+            "--pitcher-blend" => {
+                let value = args.next().unwrap_or_default();
+                pitcher_blend = match value.parse::<f32>() {
+                    Ok(weight) if (0.0..=1.0).contains(&weight) => weight,
+                    _ => {
+                        return Err(format!(
+                            "{:?} is not a blend weight between 0 and 1\n{}",
+                            value, USAGE
+                        ))
+                    }
+                };
+            }
+            _ => positional.push(arg),
+        }
+    }
+    let (team_name, seasons) = match positional.len() {
+        2 => (None, &positional[1]),
+        3 => (Some(positional[1].to_owned()), &positional[2]),
+        _ => {
+            return Err(format!(
+                "Please provide a player name, an optional team name, and a year as arguments\n{}",
+                USAGE
+            ))
+        }
+    };
+    if strategies && positional[0] == "all" {
+        return Err(format!("--strategies needs a single player\n{}", USAGE));
+    }
+    let seasons = parse_seasons(seasons)?;
+    if !matches!(seasons, Seasons::Year(_)) {
+        if positional[0] == "all" {
+            return Err(format!("all needs a single year\n{}", USAGE));
+        }
+        if strategies || umpire_range || show_pitches {
+            return Err(format!(
+                "--strategies, --umpire-range and --show-pitches need a single year\n{}",
+                USAGE
+            ));
+        }
+    }
+    Ok(Options {
+        player_name: positional[0].to_owned(),
+        team_name,
+        seasons,
+        replications,
+        seed,
+        exact,
+        rank,
+        count_zones,
+        zone_blend,
+        adapt_after,
+        adapt_over,
+        adapt_zone,
+        strategies,
+        pitcher_zones,
+        pitcher_blend,
+        umpires,
+        umpire_range,
+        platoon,
+        show_pitches,
+        games,
+    })
+}
 
-            // println!("{:?} -> {:?}", pitches_complex, pitches);
-            // println!("Outcome = {} from {}", outcome, line_data[6].to_owned());
+// How the no-bat season was evaluated
+enum NoBatResult {
+    Simulated(ReplicationSummary),
+    // Along with the expected total RE24
+    Exact(ExpectedNoBatSeason, f64),
+}
 
-            if outcome == 'N' {
-                continue;
-                // println!("Error: outcome not found for plate appearance");
+impl NoBatResult {
+    fn obp(&self) -> f32 {
+        match self {
+            NoBatResult::Simulated(summary) => summary.get_obp().mean(),
+            NoBatResult::Exact(expected, _) => expected.expected_obp() as f32,
+        }
+    }
+    fn run_value(&self) -> f32 {
+        match self {
+            NoBatResult::Simulated(summary) => summary.get_run_values().mean(),
+            NoBatResult::Exact(_, run_value) => *run_value as f32,
+        }
+    }
+    fn woba(&self, weights: &LinearWeights) -> f32 {
+        match self {
+            NoBatResult::Simulated(summary) => {
+                let lines = summary.get_batting_lines();
+                lines.iter().map(|line| weights.woba(line)).sum::<f32>() / lines.len() as f32
             }
-
-            let plate_appearance = PlateAppearance::new(
-                last_game_date.clone(),
-                player_name.to_owned(),
-                outcome,
-                pitches,
-                line_data[6].to_owned(),
-            );
-            plate_appearances.push(plate_appearance);
+            NoBatResult::Exact(expected, _) => weights.expected_woba_no_bat(expected),
         }
     }
-    return plate_appearances;
 }
 
-fn read_in_plate_appearances(
-    player_name: &String,
-    team_name: &String,
-    year: i32,
-) -> Vec<PlateAppearance> {
-    let mut plate_appearances: Vec<PlateAppearance> = Vec::new();
-    let player_id = get_player_id_from_file(player_name, team_name, year);
-    // Open the .EVA/.EVN files and grab all plate appearances for the player
-    for dir in std::fs::read_dir(format!("{DATA_DIR}/{}eve/", year)).unwrap() {
-        let path = dir.unwrap().path();
-        let path_str = path.to_str().unwrap();
-        if path_str.ends_with(".EVA") || path_str.ends_with(".EVN") {
-            plate_appearances.append(&mut read_plate_appearances_from_file(
-                &player_id,
-                player_name,
-                &path,
-            ));
+// wOBA and batting runs above average with and without a bat
+struct RunValue {
+    woba: f32,
+    runs: f32,
+    woba_no_bat: f32,
+    runs_no_bat: f32,
+}
+
+impl RunValue {
+    fn new(
+        weights: &LinearWeights,
+        plate_appearances: &[PlateAppearance],
+        no_bat: &NoBatResult,
+    ) -> Self {
+        let line = BattingLine::from_plate_appearances(plate_appearances);
+        let woba_no_bat = no_bat.woba(weights);
+        Self {
+            woba: weights.woba(&line),
+            runs: weights.batting_runs_for_line(&line),
+            woba_no_bat,
+            runs_no_bat: weights.batting_runs(woba_no_bat, plate_appearances.len() as f32),
         }
     }
-    return plate_appearances;
+    // Runs gained (or lost, if negative) by never swinging
+    fn runs_gained(&self) -> f32 {
+        self.runs_no_bat - self.runs
+    }
 }
 
-fn read_plate_discipline_from_file(player_name: &String, year: i32) -> (f32, f32, f32) {
-    let path_text = format!("{DATA_DIR}/{}eve/{}_plate_discipline.csv", year, year);
-    let path_in = Path::new(&path_text);
-    let contents = fs::read_to_string(path_in).expect("Something went wrong reading plate discipline file");
-    // Split data by newline
-    let lines = contents.split("\n");
-    println!("{}", player_name);
-    for line in lines {
-        // Remove trailing newline character
-        let mut line_trimmed = line.to_string();
-        line_trimmed.pop();
-        // Check if the line contains the player's name
-        let line_data = line_trimmed.split("\t").collect::<Vec<&str>>();
-        if line_data.len() < 10 {
-            continue;
-        }
-        if line_data[1] == player_name {
-            let mut oswing_pct_string = line_data[3].to_string();
-            oswing_pct_string.pop(); // removed percent sign
-            let oswing_pct = match oswing_pct_string.parse::<f32>() {
-                Ok(oswing_pct) => oswing_pct,
-                Err(e) => panic!(
-                    "Error parsing oswing_pct string: {}, ({})",
-                    oswing_pct_string, e
-                ),
-            };
-            let mut swing_pct_string = line_data[5].to_string();
-            swing_pct_string.pop(); // removed percent sign
-            let swing_pct = match swing_pct_string.parse::<f32>() {
-                Ok(swing_pct) => swing_pct,
-                Err(e) => panic!(
-                    "Error parsing oswing_pct string: {}, ({})",
-                    swing_pct_string, e
-                ),
-            };
-            let mut zone_pct_string = line_data[9].to_string();
-            zone_pct_string.pop(); // removed newline
-            zone_pct_string.pop(); // removed percent sign
-            let zone_pct = match zone_pct_string.parse::<f32>() {
-                Ok(zone_pct) => zone_pct,
-                Err(e) => panic!(
-                    "Error parsing zone_pct string: {}, ({})",
-                    zone_pct_string, e
-                ),
-            };
-            return (oswing_pct, swing_pct, zone_pct);
-        }
+// Umpires in the 10th and 90th percentile of strikes called on taken pitches
+static TIGHT_UMPIRE_PERCENTILE: f32 = 10.0;
+static GENEROUS_UMPIRE_PERCENTILE: f32 = 90.0;
+
+// How taken pitches are called, before any adjustment by pitchers who know he will not swing
+fn zone_model(
+    season: &Season,
+    discipline: &PlateDiscipline,
+    platoon: Option<&PlatoonSplits>,
+    options: &Options,
+) -> ZoneModel {
+    let zone_model = zone_model_without_umpires(season, discipline, platoon, options);
+    if options.umpires {
+        zone_model.with_umpires(season.get_umpire_zone_rates().clone())
+    } else {
+        zone_model
+    }
+}
+
+fn zone_model_without_umpires(
+    season: &Season,
+    discipline: &PlateDiscipline,
+    platoon: Option<&PlatoonSplits>,
+    options: &Options,
+) -> ZoneModel {
+    let zone_pct = *discipline.get_zone_pct();
+    let mut zone_model = if options.count_zones {
+        let league_zone_pct = season.get_discipline().mean_zone_pct().unwrap_or(zone_pct);
+        ZoneModel::by_count(
+            season.get_count_zone_rates(),
+            league_zone_pct,
+            zone_pct,
+            options.zone_blend,
+        )
+    } else {
+        ZoneModel::uniform(zone_pct)
+    };
+    if let Some(platoon) = platoon {
+        zone_model = zone_model.with_platoon(*platoon);
+    }
+    // Checked to be there when the season was loaded
+    match options
+        .pitcher_zones
+        .and_then(|source| season.get_pitcher_zone_rates(source).ok())
+    {
+        Some(pitchers) => zone_model.with_pitchers(pitchers.clone(), options.pitcher_blend),
+        None => zone_model,
+    }
+}
+
+// Replay the plate appearances without a bat against one zone model
+fn sim_without_bat(
+    rng: &mut ChaCha8Rng,
+    season: &Season,
+    plate_appearances: &[PlateAppearance],
+    discipline: &PlateDiscipline,
+    zone_model: &ZoneModel,
+    options: &Options,
+) -> NoBatResult {
+    let (oswing_pct, swing_pct, _) = discipline.simulation_rates();
+    let run_expectancy = season.get_run_expectancy();
+    if options.exact {
+        let expected = expected_season_no_bat(plate_appearances, oswing_pct, swing_pct, zone_model);
+        let run_value = run_expectancy.expected_total_run_value_no_bat(
+            plate_appearances,
+            oswing_pct,
+            swing_pct,
+            zone_model,
+        );
+        return NoBatResult::Exact(expected, run_value);
     }
-    (-1.0, -1.0, -1.0)
+    NoBatResult::Simulated(replicate_season_no_bat(
+        rng,
+        plate_appearances,
+        oswing_pct,
+        swing_pct,
+        zone_model,
+        run_expectancy,
+        options.replications,
+    ))
+}
+
+struct LeaderboardRow {
+    player_name: String,
+    obp: f32,
+    obp_no_bat: f32,
+    // After the league adjusts, with `--adapt-after`
+    obp_adjusted: Option<f32>,
+    // With a generous and a tight umpire, with `--umpire-range`
+    obp_umpire_range: Option<(f32, f32)>,
+    run_value: Option<RunValue>,
 }
 
-fn parse_input_arguments(args: Vec<String>) -> (String, String, i32) {
-    if args.len() < 4 {
-        if args[1] == "all" {
-            let player_name = args[1].to_owned();
-            let team_name = "".to_owned();
-            let year = args[2].parse::<i32>().unwrap();
-            return (player_name, team_name, year);
+// OBP of the plate appearances as they happened and replayed without a bat, then replayed again
+// with pitchers adjusting if `--adapt-after` was given. The full season's plate discipline is
+// used even for a single team's stint.
+fn sim_with_and_without_bat(
+    rng: &mut ChaCha8Rng,
+    season: &Season,
+    plate_appearances: &[PlateAppearance],
+    discipline: &PlateDiscipline,
+    platoon: Option<&PlatoonSplits>,
+    options: &Options,
+) -> (f32, NoBatResult, Option<NoBatResult>) {
+    let zone_model = zone_model(season, discipline, platoon, options);
+    let obp = calculate_obp(plate_appearances);
+    let no_bat = sim_without_bat(
+        rng,
+        season,
+        plate_appearances,
+        discipline,
+        &zone_model,
+        options,
+    );
+    let adjusted = options.adapt_after.map(|after| {
+        // Nobody throws more strikes than the league's best-controlled pitchers
+        let league_best = season.get_discipline().max_zone_pct().unwrap_or(100.0);
+        let target_pct = options.adapt_zone.unwrap_or(league_best).min(league_best);
+        let adaptation = PitcherAdaptation::new(after, options.adapt_over, target_pct);
+        sim_without_bat(
+            rng,
+            season,
+            plate_appearances,
+            discipline,
+            &zone_model.with_adaptation(adaptation),
+            options,
+        )
+    });
+    (obp, no_bat, adjusted)
+}
+
+// The no-bat season in front of the same umpire every day, one with a generous zone and one with
+// a tight zone
+fn sim_umpire_range(
+    rng: &mut ChaCha8Rng,
+    season: &Season,
+    plate_appearances: &[PlateAppearance],
+    discipline: &PlateDiscipline,
+    platoon: Option<&PlatoonSplits>,
+    options: &Options,
+) -> (NoBatResult, NoBatResult) {
+    let umpires = season.get_umpire_zone_rates();
+    let zone_model = zone_model_without_umpires(season, discipline, platoon, options);
+    let mut sim = |percentile: f32| {
+        let zone_model = zone_model.scaled(umpires.percentile_factor(percentile));
+        sim_without_bat(
+            rng,
+            season,
+            plate_appearances,
+            discipline,
+            &zone_model,
+            options,
+        )
+    };
+    let generous = sim(GENEROUS_UMPIRE_PERCENTILE);
+    let tight = sim(TIGHT_UMPIRE_PERCENTILE);
+    (generous, tight)
+}
+
+// Replay the plate appearances under every selective swing strategy. There is no exact version,
+// so these are always sampled.
+fn sim_strategies(
+    rng: &mut ChaCha8Rng,
+    season: &Season,
+    plate_appearances: &[PlateAppearance],
+    discipline: &PlateDiscipline,
+    platoon: Option<&PlatoonSplits>,
+    options: &Options,
+) -> Vec<(SwingStrategy, NoBatResult)> {
+    let (oswing_pct, swing_pct, _) = discipline.simulation_rates();
+    let zone_model = zone_model(season, discipline, platoon, options);
+    let pool = SwingPool::from_plate_appearances(plate_appearances);
+    SwingStrategy::all()
+        .into_iter()
+        .map(|strategy| {
+            let summary = replicate_season(
+                rng,
+                season.get_run_expectancy(),
+                options.replications,
+                |rng| {
+                    simulate_season_with_strategy(
+                        rng,
+                        plate_appearances,
+                        &strategy,
+                        &pool,
+                        oswing_pct,
+                        swing_pct,
+                        &zone_model,
+                    )
+                },
+            );
+            (strategy, NoBatResult::Simulated(summary))
+        })
+        .collect()
+}
+
+fn print_strategies(results: &[(SwingStrategy, NoBatResult)], weights: Option<&LinearWeights>) {
+    println!("Swinging only in some situations:");
+    for (strategy, result) in results {
+        match weights {
+            Some(weights) => println!(
+                "  {:<16} OBP {:.3}, wOBA {:.3}, RE24 {:+.1}",
+                strategy.name(),
+                result.obp(),
+                result.woba(weights),
+                result.run_value()
+            ),
+            None => println!(
+                "  {:<16} OBP {:.3}, RE24 {:+.1}",
+                strategy.name(),
+                result.obp(),
+                result.run_value()
+            ),
         }
-        panic!("Please provide a player name, a team name, and a year as arguments");
     }
-    let player_name = args[1].to_owned();
-    let team_name = args[2].to_owned();
-    let year = args[3].parse::<i32>().unwrap();
-    return (player_name, team_name, year);
+    let best = |value: &dyn Fn(&NoBatResult) -> f32| {
+        results
+            .iter()
+            .max_by(|a, b| value(&a.1).total_cmp(&value(&b.1)))
+            .map_or("", |(strategy, _)| strategy.name())
+    };
+    print!("  best by OBP: {}", best(&NoBatResult::obp));
+    if let Some(weights) = weights {
+        print!(", by wOBA: {}", best(&|result| result.woba(weights)));
+    }
+    println!(", by RE24: {}", best(&NoBatResult::run_value));
+}
+
+fn print_batting_lines(lines: &[(&str, &BattingLine)]) {
+    println!(
+        "{:<14}{:>5}{:>5}{:>5}{:>5}{:>4}{:>4}{:>4}{:>5}{:>5}{:>5}{:>5}{:>4}{:>4}{:>7}{:>7}{:>7}{:>7}{:>7}{:>7}{:>7}{:>6}",
+        "", "PA", "AB", "H", "1B", "2B", "3B", "HR", "BB", "IBB", "HBP", "K", "SF", "SH", "AVG",
+        "OBP", "SLG", "OPS", "BB%", "K%", "ISO", "P/PA"
+    );
+    for (label, line) in lines {
+        println!(
+            "{:<14}{:>5}{:>5}{:>5}{:>5}{:>4}{:>4}{:>4}{:>5}{:>5}{:>5}{:>5}{:>4}{:>4}{:>7.3}{:>7.3}{:>7.3}{:>7.3}{:>7.1}{:>7.1}{:>7.3}{:>6.2}",
+            label,
+            line.get_plate_appearances(),
+            line.get_at_bats(),
+            line.hits(),
+            line.get_singles(),
+            line.get_doubles(),
+            line.get_triples(),
+            line.get_home_runs(),
+            line.get_walks(),
+            line.get_intentional_walks(),
+            line.get_hit_by_pitch(),
+            line.get_strikeouts(),
+            line.get_sacrifice_flies(),
+            line.get_sacrifice_bunts(),
+            line.batting_average(),
+            line.on_base_percentage(),
+            line.slugging_percentage(),
+            line.on_base_plus_slugging(),
+            line.walk_pct(),
+            line.strikeout_pct(),
+            line.isolated_power(),
+            line.pitches_per_plate_appearance()
+        );
+    }
+}
+
+fn print_expected_season(expected: &ExpectedNoBatSeason) {
+    println!(
+        "  exact: std dev {:.4} over {} plate appearances",
+        expected.obp_std_dev(),
+        expected.get_plate_appearances()
+    );
+    println!(
+        "  expected walks {:.1}, expected strikeouts {:.1}",
+        expected.get_expected_walks(),
+        expected.get_expected_strikeouts()
+    );
+}
+
+fn print_replication_summary(summary: &ReplicationSummary) {
+    let obp = summary.get_obp();
+    let (low, high) = obp.interval(95.0);
+    println!(
+        "  mean {:.4}, std dev {:.4}, 95% interval [{:.4}, {:.4}] over {} replications",
+        obp.mean(),
+        obp.std_dev(),
+        low,
+        high,
+        summary.replications()
+    );
+    let walks = summary.get_walks();
+    let strikeouts = summary.get_strikeouts();
+    println!(
+        "  walks {:.1} +/- {:.1}, strikeouts {:.1} +/- {:.1}",
+        walks.mean(),
+        walks.std_dev(),
+        strikeouts.mean(),
+        strikeouts.std_dev()
+    );
+    println!("  distribution of OBP without a bat:");
+    for (bin_start, count) in obp.histogram(10) {
+        let bar = "#".repeat(count * 50 / summary.replications());
+        println!("  {:.4} {:>6} {}", bin_start, count, bar);
+    }
 }
 
-fn read_all_player_names(year: i32) -> Vec<(String, String)> {
-    let mut player_team_names: Vec<(String, String)> = Vec::new();
-    for dir in std::fs::read_dir(format!("{DATA_DIR}/{}eve/", year)).unwrap() {
-        let path = dir.unwrap().path();
-        let path_str = path.to_str().unwrap();
-        if path_str.ends_with(".ROS") {
-            let team_name = path_str[(path_str.len()-11)..(path_str.len()-8)].to_owned();
-            let contents_roster =
-                fs::read_to_string(path).expect("Something went wrong reading roster files");
-            let roster_lines = contents_roster.split("\n").collect::<Vec<&str>>();
-            for roster_line in roster_lines {
-                let line_data = roster_line.split(",").collect::<Vec<&str>>();
-                if line_data.len() > 2 {
-                    let mut player_name = line_data[2].to_owned();
-                    player_name.push_str(" ");
-                    player_name.push_str(line_data[1]);
-                    player_team_names.push((player_name, team_name.clone()));
+// A player's seasons one by one, each with that season's plate discipline, then added up over
+// all of them
+fn report_seasons(rng: &mut ChaCha8Rng, years: &[i32], options: &Options) -> Result<(), Error> {
+    let data_dir = Path::new(DATA_DIR);
+    let normalized_name = normalize_name(&options.player_name);
+    // Fixed by the first season he is found in, so a namesake in another era is left alone
+    let mut player_id: Option<PlayerId> = None;
+    let mut career: Vec<PlateAppearance> = Vec::new();
+    // Plate appearances times no-bat OBP, before and after the league adjusts
+    let mut on_base_no_bat = 0.0;
+    let mut on_base_adjusted = 0.0;
+    let mut run_value = 0.0;
+    let mut run_value_no_bat = 0.0;
+    let mut seasons = 0;
+    for &year in years {
+        // Rosters are cheap to read, so seasons he did not play in are not parsed
+        let on_roster = read_season_rosters(data_dir, year)?
+            .iter()
+            .any(|entry| match &player_id {
+                Some(player_id) => entry.get_id() == player_id,
+                None => {
+                    entry.get_id() == &options.player_name
+                        || normalize_name(&entry.get_full_name()) == normalized_name
                 }
+            });
+        if !on_roster {
+            continue;
+        }
+        let season = Season::load_games(data_dir, year, options.games)?;
+        print_warnings(&season);
+        if let Some(source) = options.pitcher_zones {
+            season.get_pitcher_zone_rates(source)?;
+        }
+        let id = match &player_id {
+            Some(player_id) => player_id.clone(),
+            None => match season.find_player_id(&options.player_name, options.team_name.as_deref())
+            {
+                Ok(player_id) => player_id,
+                // On another team's roster that season
+                Err(Error::UnknownPlayer { .. }) => continue,
+                Err(e) => return Err(e),
+            },
+        };
+        let plate_appearances = season.get_plate_appearances(&id);
+        if plate_appearances.is_empty() {
+            continue;
+        }
+        if player_id.is_none() {
+            let games = match options.games {
+                GameSelection::Regular => "",
+                GameSelection::Postseason => ", postseason",
+                GameSelection::AllStar => ", All-Star games",
+                GameSelection::All => ", all games",
+            };
+            println!(
+                "OBP for {} without a bat by season{}:",
+                options.player_name, games
+            );
+            player_id = Some(id.clone());
+        }
+        let teams = season
+            .get_stints(&id)
+            .iter()
+            .map(|stint| stint.get_team().to_owned())
+            .collect::<Vec<String>>()
+            .join("/");
+        let discipline = match season.get_plate_discipline(&id) {
+            Ok(discipline) => discipline,
+            Err(Error::MissingDiscipline { .. }) => {
+                println!(
+                    "  {} {}: {} plate appearances, no plate discipline data, skipped",
+                    year,
+                    teams,
+                    plate_appearances.len()
+                );
+                continue;
             }
+            Err(e) => return Err(e),
+        };
+        let platoon = match options.platoon {
+            true => Some(season.get_platoon_splits(&id)?),
+            false => None,
+        };
+        let (obp, no_bat, adjusted) = sim_with_and_without_bat(
+            rng,
+            &season,
+            plate_appearances,
+            &discipline,
+            platoon.as_ref(),
+            options,
+        );
+        let mut line = format!(
+            "  {} {}: {} plate appearances, OBP {:.3}, without a bat {:.3}",
+            year,
+            teams,
+            plate_appearances.len(),
+            obp,
+            no_bat.obp()
+        );
+        if let Some(adjusted) = &adjusted {
+            line += &format!(", after the league adjusts {:.3}", adjusted.obp());
+            on_base_adjusted += adjusted.obp() * plate_appearances.len() as f32;
         }
+        println!("{}", line);
+        on_base_no_bat += no_bat.obp() * plate_appearances.len() as f32;
+        run_value += season
+            .get_run_expectancy()
+            .total_run_value(plate_appearances);
+        run_value_no_bat += no_bat.run_value();
+        career.extend(plate_appearances.iter().cloned());
+        seasons += 1;
+    }
+    if player_id.is_none() {
+        return Err(Error::UnknownPlayerInSeasons {
+            name: options.player_name.to_owned(),
+            years: years
+                .first()
+                .zip(years.last())
+                .map(|(&first, &last)| (first, last)),
+        });
+    }
+    if seasons == 0 {
+        return Ok(());
+    }
+    // Every plate appearance counts toward OBP once the bat is gone
+    let mut line = format!(
+        "Total over {} season{}: {} plate appearances, OBP {:.3}, without a bat {:.3}",
+        seasons,
+        if seasons == 1 { "" } else { "s" },
+        career.len(),
+        calculate_obp(&career),
+        on_base_no_bat / career.len() as f32
+    );
+    if options.adapt_after.is_some() {
+        line += &format!(
+            ", after the league adjusts {:.3}",
+            on_base_adjusted / career.len() as f32
+        );
     }
-    return player_team_names;
+    println!("{}", line);
+    println!(
+        "RE24 {:+.1}, without a bat {:+.1}",
+        run_value, run_value_no_bat
+    );
+    Ok(())
 }
 
-fn sim_player_with_and_without_bat(player_name: &String, team_name: &String, year: i32) -> (f32, f32) {
-    let (oswing_pct, swing_pct, zone_pct) = read_plate_discipline_from_file(player_name, year);
-    if oswing_pct == -1.0 {
-        // probably a pitcher
-        return (0.0, 0.0);
+// Event file records the season was loaded without
+fn print_warnings(season: &Season) {
+    for warning in season.get_warnings() {
+        eprintln!("Warning: {}, skipped", warning);
     }
-    let plate_appearances = read_in_plate_appearances(player_name, team_name, year);
-    let mut plate_appearances_no_bat: Vec<PlateAppearance> = Vec::new();
-    for appearance in &plate_appearances {
-        plate_appearances_no_bat.push(simulate_plate_appearance_no_bat(
-            appearance, oswing_pct, swing_pct, zone_pct,
-        ));
+}
+
+// The year, followed by the kind of games when they are not the regular season
+fn season_label(year: i32, games: GameSelection) -> String {
+    match games {
+        GameSelection::Regular => year.to_string(),
+        GameSelection::Postseason => format!("the {} postseason", year),
+        GameSelection::AllStar => format!("the {} All-Star game", year),
+        GameSelection::All => format!("{}, all games", year),
     }
-    let obp = calculate_obp(&plate_appearances);
-    let obp_no_bat = calculate_obp(&plate_appearances_no_bat);
-    return (obp, obp_no_bat);
 }
 
 fn main() {
     // Collect input arguments
     let args: Vec<String> = std::env::args().collect();
-    let (player_name, team_name, year) = parse_input_arguments(args);
+    let options = match parse_input_arguments(args) {
+        Ok(options) => options,
+        Err(message) => {
+            eprintln!("{}", message);
+            std::process::exit(2);
+        }
+    };
+    let player_name = &options.player_name;
+
+    // A seeded ChaCha generator gives the same draws on every platform and rand release
+    let mut rng = match options.seed {
+        Some(seed) => ChaCha8Rng::seed_from_u64(seed),
+        None => ChaCha8Rng::from_entropy(),
+    };
+
+    let year = match options.seasons {
+        Seasons::Year(year) => year,
+        seasons => {
+            let years = match list_season_years(Path::new(DATA_DIR)) {
+                Ok(years) => years,
+                Err(e) => {
+                    eprintln!("Error: {}", e);
+                    std::process::exit(1);
+                }
+            };
+            let years = match seasons {
+                Seasons::Range(first, last) => years
+                    .into_iter()
+                    .filter(|year| (first..=last).contains(year))
+                    .collect(),
+                _ => years,
+            };
+            if let Err(e) = report_seasons(&mut rng, &years, &options) {
+                eprintln!("Error: {}", e);
+                std::process::exit(1);
+            }
+            return;
+        }
+    };
+
+    // Every event file of the season is parsed once up front
+    let season = match Season::load_games(Path::new(DATA_DIR), year, options.games) {
+        Ok(season) => season,
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            std::process::exit(1);
+        }
+    };
+    print_warnings(&season);
+
+    if let Some(source) = options.pitcher_zones {
+        if let Err(e) = season.get_pitcher_zone_rates(source) {
+            eprintln!("Error: {}", e);
+            std::process::exit(1);
+        }
+    }
+
+    // wOBA weights are optional unless the leaderboard is ranked by them
+    let weights = match read_linear_weights(Path::new(DATA_DIR), year) {
+        Ok(weights) => Some(weights),
+        Err(e) if options.rank == Rank::Runs => {
+            eprintln!("Error: {}", e);
+            std::process::exit(1);
+        }
+        Err(_) => None,
+    };
 
     // If player_name argument is "all", then return a top 20 list of players with the highest OBP without a bat
     if player_name == "all" {
-        let player_team_names = read_all_player_names(year);
-        let mut obp_no_bat_list: Vec<(String, f32, f32)> = Vec::new();
-        for (player_name, team_name) in player_team_names {
-            let (obp, obp_no_bat) = sim_player_with_and_without_bat(&player_name, &team_name, year);
-            obp_no_bat_list.push((player_name, obp, obp_no_bat));
-        }
-        obp_no_bat_list.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap());
-        println!("Top 20 OBP without a bat in {}", year);
-        for (i, (player_name, obp, obp_no_bat)) in obp_no_bat_list.iter().enumerate() {
-            if i >= 20 {
-                break;
+        let mut obp_no_bat_list: Vec<LeaderboardRow> = Vec::new();
+        // Traded players are on more than one roster but are simulated once for the season
+        let mut seen_player_ids: HashSet<&String> = HashSet::new();
+        for entry in season.get_roster() {
+            if !seen_player_ids.insert(entry.get_id()) {
+                continue;
+            }
+            // Nobody who did not bat in the games picked
+            if season.get_plate_appearances(entry.get_id()).is_empty() {
+                continue;
+            }
+            let player_name = entry.get_full_name();
+            // The player's whole season, across every team he played for
+            let batter = season
+                .get_plate_discipline(entry.get_id())
+                .and_then(|discipline| {
+                    let platoon = match options.platoon {
+                        true => Some(season.get_platoon_splits(entry.get_id())?),
+                        false => None,
+                    };
+                    Ok((discipline, platoon))
+                });
+            match batter {
+                Ok((discipline, platoon)) => {
+                    let plate_appearances = season.get_plate_appearances(entry.get_id());
+                    let (obp, no_bat, adjusted) = sim_with_and_without_bat(
+                        &mut rng,
+                        &season,
+                        plate_appearances,
+                        &discipline,
+                        platoon.as_ref(),
+                        &options,
+                    );
+                    let run_value = weights
+                        .as_ref()
+                        .map(|weights| RunValue::new(weights, plate_appearances, &no_bat));
+                    let obp_umpire_range = options.umpire_range.then(|| {
+                        let (generous, tight) = sim_umpire_range(
+                            &mut rng,
+                            &season,
+                            plate_appearances,
+                            &discipline,
+                            platoon.as_ref(),
+                            &options,
+                        );
+                        (generous.obp(), tight.obp())
+                    });
+                    obp_no_bat_list.push(LeaderboardRow {
+                        player_name,
+                        obp,
+                        obp_no_bat: no_bat.obp(),
+                        obp_adjusted: adjusted.as_ref().map(NoBatResult::obp),
+                        obp_umpire_range,
+                        run_value,
+                    })
+                }
+                // No plate discipline row, probably a pitcher
+                Err(Error::MissingDiscipline { .. }) => {}
+                Err(e) => eprintln!("Skipping {} ({}): {}", player_name, entry.get_id(), e),
+            }
+        }
+        match options.rank {
+            Rank::Obp => {
+                obp_no_bat_list.sort_by(|a, b| b.obp_no_bat.total_cmp(&a.obp_no_bat));
+                println!(
+                    "Top 20 OBP without a bat in {}",
+                    season_label(year, options.games)
+                );
+            }
+            Rank::Runs => {
+                let runs_gained = |row: &LeaderboardRow| {
+                    row.run_value
+                        .as_ref()
+                        .map_or(f32::NAN, RunValue::runs_gained)
+                };
+                obp_no_bat_list.sort_by(|a, b| runs_gained(b).total_cmp(&runs_gained(a)));
+                println!(
+                    "Top 20 batting runs gained without a bat in {}",
+                    season_label(year, options.games)
+                );
+            }
+        }
+        for row in obp_no_bat_list.iter().take(20) {
+            let mut line = format!("{}: {}, {}", row.player_name, row.obp, row.obp_no_bat);
+            if let Some(adjusted) = row.obp_adjusted {
+                line += &format!(", {}", adjusted);
+            }
+            if let Some((generous, tight)) = row.obp_umpire_range {
+                line += &format!(", umpires {:.3} to {:.3}", generous, tight);
+            }
+            if let Some(run_value) = &row.run_value {
+                line += &format!(
+                    ", runs {:+.1}, {:+.1} ({:+.1})",
+                    run_value.runs,
+                    run_value.runs_no_bat,
+                    run_value.runs_gained()
+                );
             }
-            println!("{}: {}, {}", player_name, obp, obp_no_bat);
+            println!("{}", line);
         }
-        return;
     } else {
-         // Calculate the player's OBP for 2023 with and without bat
-        let (obp, obp_no_bat) = sim_player_with_and_without_bat(&player_name, &team_name, year);
-        println!(
-            "OBP for {} in {}: {}",
-            player_name,
-            year,
-            obp
+        // Calculate the player's OBP for the season with and without bat
+        let (player_id, discipline, platoon) = match season
+            .find_player_id(player_name, options.team_name.as_deref())
+            .and_then(|player_id| {
+                let discipline = season.get_plate_discipline(&player_id)?;
+                let platoon = match options.platoon {
+                    true => Some(season.get_platoon_splits(&player_id)?),
+                    false => None,
+                };
+                Ok((player_id, discipline, platoon))
+            }) {
+            Ok(result) => result,
+            Err(e) => {
+                eprintln!("Error: {}", e);
+                std::process::exit(1);
+            }
+        };
+        let year = season_label(year, options.games);
+        if season.get_plate_appearances(&player_id).is_empty() {
+            eprintln!(
+                "Error: {} has no plate appearances in {}",
+                player_name, year
+            );
+            std::process::exit(1);
+        }
+        // Plate appearances whose pitches cannot be replayed as recorded
+        let pitch_data = |kind: PitchData| {
+            season
+                .get_plate_appearances(&player_id)
+                .iter()
+                .filter(|appearance| appearance.pitch_data() == kind)
+                .count()
+        };
+        let (missing, inconsistent) = (
+            pitch_data(PitchData::Missing),
+            pitch_data(PitchData::Inconsistent),
         );
+        if missing + inconsistent > 0 {
+            println!(
+                "{} plate appearances without pitches and {} contradicting their recorded count, replayed from the count where it is known",
+                missing, inconsistent
+            );
+        }
+        if options.show_pitches {
+            for appearance in season.get_plate_appearances(&player_id) {
+                println!("{}", appearance);
+            }
+        }
+        let (obp, no_bat, adjusted) = sim_with_and_without_bat(
+            &mut rng,
+            &season,
+            season.get_plate_appearances(&player_id),
+            &discipline,
+            platoon.as_ref(),
+            &options,
+        );
+        println!("OBP for {} in {}: {}", player_name, year, obp);
         println!(
             "OBP for {} in {} without a bat: {}",
             player_name,
             year,
-            obp_no_bat
+            no_bat.obp()
+        );
+        if let Some(adjusted) = &adjusted {
+            println!(
+                "OBP for {} in {} without a bat after the league adjusts: {} (RE24 {:+.1})",
+                player_name,
+                year,
+                adjusted.obp(),
+                adjusted.run_value()
+            );
+        }
+        if options.umpire_range {
+            let (generous, tight) = sim_umpire_range(
+                &mut rng,
+                &season,
+                season.get_plate_appearances(&player_id),
+                &discipline,
+                platoon.as_ref(),
+                &options,
+            );
+            let umpires = season.get_umpire_zone_rates();
+            println!(
+                "  with a generous zone ({:.0}% more strikes): {}",
+                100.0 * (umpires.percentile_factor(GENEROUS_UMPIRE_PERCENTILE) - 1.0),
+                generous.obp()
+            );
+            println!(
+                "  with a tight zone ({:.0}% fewer strikes): {}",
+                100.0 * (1.0 - umpires.percentile_factor(TIGHT_UMPIRE_PERCENTILE)),
+                tight.obp()
+            );
+        }
+        if let Some(platoon) = &platoon {
+            // The same replay split by the hand of the pitcher he faced
+            let zone_model = zone_model(&season, &discipline, Some(platoon), &options);
+            for hand in [Hand::Left, Hand::Right] {
+                let plate_appearances = season
+                    .get_plate_appearances(&player_id)
+                    .iter()
+                    .filter(|appearance| appearance.get_pitcher_hand() == &Some(hand))
+                    .cloned()
+                    .collect::<Vec<PlateAppearance>>();
+                if plate_appearances.is_empty() {
+                    continue;
+                }
+                let no_bat = sim_without_bat(
+                    &mut rng,
+                    &season,
+                    &plate_appearances,
+                    &discipline,
+                    &zone_model,
+                    &options,
+                );
+                println!(
+                    "  vs {}HP: {} plate appearances, Zone% {:.1}, OBP {:.3}, without a bat {:.3}",
+                    hand.to_code(),
+                    plate_appearances.len(),
+                    platoon.get(hand).2,
+                    calculate_obp(&plate_appearances),
+                    no_bat.obp()
+                );
+            }
+        }
+        let real_run_value = season
+            .get_run_expectancy()
+            .total_run_value(season.get_plate_appearances(&player_id));
+        println!(
+            "RE24 {:+.1}, without a bat {:+.1}",
+            real_run_value,
+            no_bat.run_value()
         );
+        if let Some(weights) = &weights {
+            let run_value =
+                RunValue::new(weights, season.get_plate_appearances(&player_id), &no_bat);
+            println!(
+                "wOBA {:.3}, without a bat {:.3}",
+                run_value.woba, run_value.woba_no_bat
+            );
+            println!(
+                "Batting runs above average {:+.1}, without a bat {:+.1} ({:+.1} by not swinging)",
+                run_value.runs,
+                run_value.runs_no_bat,
+                run_value.runs_gained()
+            );
+        }
+        match &no_bat {
+            NoBatResult::Simulated(summary) if summary.replications() > 1 => {
+                print_replication_summary(summary)
+            }
+            NoBatResult::Simulated(_) => {}
+            NoBatResult::Exact(expected, _) => print_expected_season(expected),
+        }
+
+        // Only a simulated season has a line of its own; without a bat every hit is gone. With
+        // more than one replication it is just the first of them, the means are the ones above.
+        let real_line =
+            BattingLine::from_plate_appearances(season.get_plate_appearances(&player_id));
+        let mut lines = vec![("With a bat", &real_line)];
+        if let NoBatResult::Simulated(summary) = &no_bat {
+            let label = if summary.replications() > 1 {
+                println!(
+                    "Replay 1 is a single sample of the {} replications",
+                    summary.replications()
+                );
+                "Replay 1"
+            } else {
+                "Without a bat"
+            };
+            lines.push((label, &summary.get_batting_lines()[0]));
+        }
+        print_batting_lines(&lines);
+
+        if options.strategies {
+            let results = sim_strategies(
+                &mut rng,
+                &season,
+                season.get_plate_appearances(&player_id),
+                &discipline,
+                platoon.as_ref(),
+                &options,
+            );
+            print_strategies(&results, weights.as_ref());
+        }
+
+        let stints = season.get_stints(&player_id);
+        if stints.len() > 1 {
+            println!("By team:");
+            for stint in &stints {
+                let (obp, no_bat, adjusted) = sim_with_and_without_bat(
+                    &mut rng,
+                    &season,
+                    stint.get_plate_appearances(),
+                    &discipline,
+                    platoon.as_ref(),
+                    &options,
+                );
+                match adjusted {
+                    Some(adjusted) => println!(
+                        "  {}: {} plate appearances, OBP {}, without a bat {}, after the league adjusts {}",
+                        stint.get_team(),
+                        stint.get_plate_appearances().len(),
+                        obp,
+                        no_bat.obp(),
+                        adjusted.obp()
+                    ),
+                    None => println!(
+                        "  {}: {} plate appearances, OBP {}, without a bat {}",
+                        stint.get_team(),
+                        stint.get_plate_appearances().len(),
+                        obp,
+                        no_bat.obp()
+                    ),
+                }
+            }
+        }
     }
 }
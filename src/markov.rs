@@ -0,0 +1,357 @@
+use crate::zone_model::ZoneModel;
+use crate::{PitchCode, PitchData, PlateAppearance, PlateOutcome};
+
+// Without a bat the rest of a plate appearance is an absorbing Markov chain over the 12
+// ball-strike counts: each pitch is a called strike with the zone model's probability for the
+// count and a ball otherwise, until four balls (walk) or three strikes (strikeout).
+
+// Probability of drawing a walk from each count, indexed [balls][strikes]
+pub fn walk_probability_table(zone_model: &ZoneModel) -> [[f64; 3]; 4] {
+    let mut table = [[0.0; 3]; 4];
+    for balls in (0..4).rev() {
+        for strikes in (0..3).rev() {
+            let strike = (zone_model.strike_pct(balls as i32, strikes as i32) as f64 / 100.0)
+                .clamp(0.0, 1.0);
+            let after_ball = if balls == 3 {
+                1.0
+            } else {
+                table[balls + 1][strikes]
+            };
+            let after_strike = if strikes == 2 {
+                0.0
+            } else {
+                table[balls][strikes + 1]
+            };
+            table[balls][strikes] = strike * after_strike + (1.0 - strike) * after_ball;
+        }
+    }
+    table
+}
+
+// Probability of a walk from a given count if the batter never swings again
+pub fn walk_probability_from_count(zone_model: &ZoneModel, balls: i32, strikes: i32) -> f64 {
+    if balls >= 4 {
+        return 1.0;
+    }
+    if strikes >= 3 {
+        return 0.0;
+    }
+    walk_probability_table(zone_model)[balls.max(0) as usize][strikes.max(0) as usize]
+}
+
+// Exact chances of each way a plate appearance can end without a bat
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct NoBatProbabilities {
+    walk: f64,
+    intentional_walk: f64,
+    hit_by_pitch: f64,
+    strikeout: f64,
+}
+
+impl NoBatProbabilities {
+    pub fn get_walk(&self) -> &f64 {
+        &self.walk
+    }
+    pub fn get_intentional_walk(&self) -> &f64 {
+        &self.intentional_walk
+    }
+    pub fn get_hit_by_pitch(&self) -> &f64 {
+        &self.hit_by_pitch
+    }
+    pub fn get_strikeout(&self) -> &f64 {
+        &self.strikeout
+    }
+    pub fn on_base(&self) -> f64 {
+        self.walk + self.intentional_walk + self.hit_by_pitch
+    }
+}
+
+// The same replay as `simulate_plate_appearance_no_bat`, but every re-simulated swing splits the
+// probability mass between a ball and a strike instead of drawing one of them
+pub fn plate_appearance_no_bat_probabilities(
+    appearance: &PlateAppearance,
+    oswing_pct: f32,
+    swing_pct: f32,
+    zone_model: &ZoneModel,
+) -> NoBatProbabilities {
+    let mut probabilities = NoBatProbabilities::default();
+    if appearance.get_outcome() == &PlateOutcome::IntentionalWalk {
+        probabilities.intentional_walk = 1.0;
+        return probabilities;
+    }
+    let fallback_count = appearance.fallback_count();
+    if appearance.pitch_data() != PitchData::Complete {
+        match appearance.get_outcome() {
+            PlateOutcome::Walk => {
+                probabilities.walk = 1.0;
+                return probabilities;
+            }
+            PlateOutcome::HitByPitch => {
+                probabilities.hit_by_pitch = 1.0;
+                return probabilities;
+            }
+            _ => {}
+        }
+    }
+    let prob_ball_given_swing =
+        (zone_model.ball_given_swing_pct(oswing_pct, swing_pct) / 100.0).clamp(0.0, 1.0) as f64;
+    // Probability of being in each count that has not ended the plate appearance yet. Without
+    // trustworthy pitches it all starts at the recorded count.
+    let mut counts = [[0.0; 3]; 4];
+    let real_pitches = match fallback_count {
+        Some((balls, strikes)) => {
+            counts[balls as usize][strikes as usize] = 1.0;
+            &[][..]
+        }
+        None => {
+            counts[0][0] = 1.0;
+            &appearance.get_pitches()[..]
+        }
+    };
+    for pitch in real_pitches {
+        let mut next = [[0.0; 3]; 4];
+        for balls in 0..4 {
+            for strikes in 0..3 {
+                let mass = counts[balls][strikes];
+                if mass == 0.0 {
+                    continue;
+                }
+                let (ball, strike, hit) = match pitch {
+                    PitchCode::Ball
+                    | PitchCode::IntentionalBall
+                    | PitchCode::Pitchout
+                    | PitchCode::AutomaticBall
+                    | PitchCode::SwingingOnPitchout
+                    | PitchCode::FoulOnPitchout
+                    | PitchCode::InPlayOnPitchout => (1.0, 0.0, 0.0),
+                    PitchCode::AutomaticStrike
+                    | PitchCode::CalledStrike
+                    | PitchCode::StrikeUnknownType => (0.0, 1.0, 0.0),
+                    PitchCode::HitBatter => (0.0, 0.0, 1.0),
+                    PitchCode::Foul
+                    | PitchCode::FoulBunt
+                    | PitchCode::MissedBunt
+                    | PitchCode::FoulTipOnBunt
+                    | PitchCode::SwingingStrike
+                    | PitchCode::FoulTip
+                    | PitchCode::InPlay => {
+                        (prob_ball_given_swing, 1.0 - prob_ball_given_swing, 0.0)
+                    }
+                    PitchCode::Unknown
+                    | PitchCode::NoPitch
+                    | PitchCode::CatcherPickoffThrow
+                    | PitchCode::BlockedByCatcher
+                    | PitchCode::NonBatterPlay
+                    | PitchCode::PickoffThrow(_)
+                    | PitchCode::RunnerGoing => {
+                        next[balls][strikes] += mass;
+                        continue;
+                    }
+                };
+                probabilities.hit_by_pitch += mass * hit;
+                if balls == 3 {
+                    probabilities.walk += mass * ball;
+                } else {
+                    next[balls + 1][strikes] += mass * ball;
+                }
+                if strikes == 2 {
+                    probabilities.strikeout += mass * strike;
+                } else {
+                    next[balls][strikes + 1] += mass * strike;
+                }
+            }
+        }
+        counts = next;
+    }
+
+    // Whatever is left is still at the plate and sees nothing but taken pitches from here
+    let table = walk_probability_table(zone_model);
+    for balls in 0..4 {
+        for strikes in 0..3 {
+            let mass = counts[balls][strikes];
+            probabilities.walk += mass * table[balls][strikes];
+            probabilities.strikeout += mass * (1.0 - table[balls][strikes]);
+        }
+    }
+    probabilities
+}
+
+// Expected no-bat season line with no Monte Carlo noise
+#[derive(Clone, Copy, Debug)]
+pub struct ExpectedNoBatSeason {
+    plate_appearances: usize,
+    expected_on_base: f64,
+    on_base_variance: f64,
+    expected_walks: f64,
+    expected_intentional_walks: f64,
+    expected_hit_by_pitch: f64,
+    expected_strikeouts: f64,
+}
+
+impl ExpectedNoBatSeason {
+    pub fn get_plate_appearances(&self) -> &usize {
+        &self.plate_appearances
+    }
+    // Walks of both kinds plus hit batters
+    pub fn get_expected_on_base(&self) -> &f64 {
+        &self.expected_on_base
+    }
+    // Includes intentional walks
+    pub fn get_expected_walks(&self) -> &f64 {
+        &self.expected_walks
+    }
+    pub fn get_expected_intentional_walks(&self) -> &f64 {
+        &self.expected_intentional_walks
+    }
+    pub fn get_expected_hit_by_pitch(&self) -> &f64 {
+        &self.expected_hit_by_pitch
+    }
+    pub fn get_expected_strikeouts(&self) -> &f64 {
+        &self.expected_strikeouts
+    }
+    pub fn expected_obp(&self) -> f64 {
+        self.expected_on_base / self.plate_appearances as f64
+    }
+    // Every plate appearance is an independent Bernoulli trial, so the variance of OBP is
+    // sum(p * (1 - p)) / PA^2
+    pub fn obp_variance(&self) -> f64 {
+        self.on_base_variance / (self.plate_appearances as f64).powi(2)
+    }
+    pub fn obp_std_dev(&self) -> f64 {
+        self.obp_variance().sqrt()
+    }
+}
+
+pub fn expected_season_no_bat(
+    plate_appearances: &[PlateAppearance],
+    oswing_pct: f32,
+    swing_pct: f32,
+    zone_model: &ZoneModel,
+) -> ExpectedNoBatSeason {
+    let mut season = ExpectedNoBatSeason {
+        plate_appearances: plate_appearances.len(),
+        expected_on_base: 0.0,
+        on_base_variance: 0.0,
+        expected_walks: 0.0,
+        expected_intentional_walks: 0.0,
+        expected_hit_by_pitch: 0.0,
+        expected_strikeouts: 0.0,
+    };
+    for (index, appearance) in plate_appearances.iter().enumerate() {
+        let probabilities = plate_appearance_no_bat_probabilities(
+            appearance,
+            oswing_pct,
+            swing_pct,
+            &zone_model.for_plate_appearance(index, appearance),
+        );
+        let on_base = probabilities.on_base();
+        season.expected_on_base += on_base;
+        season.on_base_variance += on_base * (1.0 - on_base);
+        season.expected_walks += probabilities.walk + probabilities.intentional_walk;
+        season.expected_intentional_walks += probabilities.intentional_walk;
+        season.expected_hit_by_pitch += probabilities.hit_by_pitch;
+        season.expected_strikeouts += probabilities.strikeout;
+    }
+    season
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::strategy::{simulate_plate_appearance_with_strategy, SwingPool, SwingStrategy};
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
+    const REPLAYS: u64 = 2000;
+
+    // How often the sampled replay ends in a walk, a hit batter and a strikeout
+    fn sampled(appearance: &PlateAppearance, zone_model: &ZoneModel) -> (f64, f64, f64) {
+        let mut counts = (0, 0, 0);
+        for seed in 0..REPLAYS {
+            let replayed = simulate_plate_appearance_with_strategy(
+                &mut ChaCha8Rng::seed_from_u64(seed),
+                appearance,
+                &SwingStrategy::Never,
+                &SwingPool::default(),
+                25.0,
+                45.0,
+                zone_model,
+            );
+            match replayed.get_outcome() {
+                PlateOutcome::Walk => counts.0 += 1,
+                PlateOutcome::HitByPitch => counts.1 += 1,
+                PlateOutcome::Strikeout => counts.2 += 1,
+                outcome => panic!("replayed to {}", outcome),
+            }
+        }
+        let share = |count: u64| count as f64 / REPLAYS as f64;
+        (share(counts.0), share(counts.1), share(counts.2))
+    }
+
+    #[test]
+    fn sampled_replay_agrees_with_the_exact_one() {
+        use PlateOutcome::*;
+        let fixture = PlateAppearance::fixture;
+        // Appearance, Zone% and the exact chances of a walk, a hit batter and a strikeout
+        let cases = [
+            // A `??` count with no pitches leaves ball four and a hit batter standing
+            (fixture(Walk, ""), 100.0, Some((1.0, 0.0, 0.0))),
+            (fixture(HitByPitch, ""), 100.0, Some((0.0, 1.0, 0.0))),
+            // So do unknown pitches
+            (fixture(Walk, "BUB"), 100.0, Some((1.0, 0.0, 0.0))),
+            // Missing pitches start from the recorded count
+            (
+                fixture(FieldedOut, "").with_count(2, 1),
+                0.0,
+                Some((1.0, 0.0, 0.0)),
+            ),
+            (
+                fixture(FieldedOut, "").with_count(2, 1),
+                100.0,
+                Some((0.0, 0.0, 1.0)),
+            ),
+            // Three balls before the final pitch, but the play says 0-2
+            (
+                fixture(FieldedOut, "BBBX").with_count(0, 2),
+                100.0,
+                Some((0.0, 0.0, 1.0)),
+            ),
+            (
+                fixture(Walk, "BBCB").with_count(3, 2),
+                100.0,
+                Some((1.0, 0.0, 0.0)),
+            ),
+            // Strikes of unknown type are strikes
+            (
+                fixture(Strikeout, "KKC").with_count(0, 2),
+                0.0,
+                Some((0.0, 0.0, 1.0)),
+            ),
+            (fixture(FieldedOut, "").with_count(2, 1), 45.0, None),
+            (fixture(FieldedOut, "BSFBX").with_count(2, 2), 45.0, None),
+            (fixture(HitByPitch, "BSH").with_count(1, 1), 45.0, None),
+        ];
+        for (appearance, zone_pct, expected) in cases {
+            let zone_model = ZoneModel::uniform(zone_pct);
+            let exact = plate_appearance_no_bat_probabilities(&appearance, 25.0, 45.0, &zone_model);
+            let exact = (
+                *exact.get_walk(),
+                *exact.get_hit_by_pitch(),
+                *exact.get_strikeout(),
+            );
+            if let Some(expected) = expected {
+                assert_eq!(exact, expected, "{} at {}", appearance, zone_pct);
+            }
+            let sampled = sampled(&appearance, &zone_model);
+            let close = |a: f64, b: f64| (a - b).abs() < 0.04;
+            assert!(
+                close(sampled.0, exact.0) && close(sampled.1, exact.1) && close(sampled.2, exact.2),
+                "{} at {}: sampled {:?}, exact {:?}",
+                appearance,
+                zone_pct,
+                sampled,
+                exact
+            );
+        }
+    }
+}
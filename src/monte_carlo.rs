@@ -0,0 +1,259 @@
+use rand::Rng;
+
+use crate::run_expectancy::RunExpectancy;
+use crate::stat_line::BattingLine;
+use crate::zone_model::ZoneModel;
+use crate::{simulate_plate_appearance_no_bat, PlateAppearance};
+
+// Sorted sample of a statistic over many simulated seasons
+#[derive(Clone, Debug)]
+pub struct Distribution {
+    values: Vec<f32>,
+}
+
+impl Distribution {
+    pub fn new(mut values: Vec<f32>) -> Self {
+        values.sort_by(|a, b| a.total_cmp(b));
+        Self { values }
+    }
+    // Every replication's value, smallest first
+    pub fn get_values(&self) -> &Vec<f32> {
+        &self.values
+    }
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+    pub fn mean(&self) -> f32 {
+        if self.values.is_empty() {
+            return f32::NAN;
+        }
+        self.values.iter().sum::<f32>() / self.values.len() as f32
+    }
+    // Sample standard deviation
+    pub fn std_dev(&self) -> f32 {
+        if self.values.len() < 2 {
+            return 0.0;
+        }
+        let mean = self.mean();
+        let sum_of_squares = self
+            .values
+            .iter()
+            .map(|value| (value - mean) * (value - mean))
+            .sum::<f32>();
+        (sum_of_squares / (self.values.len() - 1) as f32).sqrt()
+    }
+    // Linearly interpolated percentile, `percent` between 0 and 100
+    pub fn percentile(&self, percent: f32) -> f32 {
+        if self.values.is_empty() {
+            return f32::NAN;
+        }
+        let rank = (percent.clamp(0.0, 100.0) / 100.0) * (self.values.len() - 1) as f32;
+        let lower = rank.floor() as usize;
+        let upper = rank.ceil() as usize;
+        let weight = rank - lower as f32;
+        self.values[lower] * (1.0 - weight) + self.values[upper] * weight
+    }
+    // Central interval holding `level` percent of the replications, e.g. 95.0
+    pub fn interval(&self, level: f32) -> (f32, f32) {
+        let tail = (100.0 - level) / 2.0;
+        (self.percentile(tail), self.percentile(100.0 - tail))
+    }
+    // Counts of values falling in `bins` equal-width bins between the min and max
+    pub fn histogram(&self, bins: usize) -> Vec<(f32, usize)> {
+        if self.values.is_empty() || bins == 0 {
+            return Vec::new();
+        }
+        let min = self.values[0];
+        let max = self.values[self.values.len() - 1];
+        let width = if max > min {
+            (max - min) / bins as f32
+        } else {
+            1.0
+        };
+        let mut counts = vec![0; bins];
+        for value in &self.values {
+            let bin = std::cmp::min(((value - min) / width) as usize, bins - 1);
+            counts[bin] += 1;
+        }
+        counts
+            .into_iter()
+            .enumerate()
+            .map(|(i, count)| (min + width * i as f32, count))
+            .collect()
+    }
+}
+
+// No-bat OBP, walks, strikeouts and RE24 over repeated simulations of the same season, along
+// with each simulated season's full batting line
+#[derive(Clone, Debug)]
+pub struct ReplicationSummary {
+    obp: Distribution,
+    walks: Distribution,
+    strikeouts: Distribution,
+    run_values: Distribution,
+    batting_lines: Vec<BattingLine>,
+}
+
+impl ReplicationSummary {
+    pub fn new(
+        obp: Distribution,
+        walks: Distribution,
+        strikeouts: Distribution,
+        run_values: Distribution,
+        batting_lines: Vec<BattingLine>,
+    ) -> Self {
+        Self {
+            obp,
+            walks,
+            strikeouts,
+            run_values,
+            batting_lines,
+        }
+    }
+    pub fn get_obp(&self) -> &Distribution {
+        &self.obp
+    }
+    pub fn get_walks(&self) -> &Distribution {
+        &self.walks
+    }
+    pub fn get_strikeouts(&self) -> &Distribution {
+        &self.strikeouts
+    }
+    // Total RE24 of each simulated season
+    pub fn get_run_values(&self) -> &Distribution {
+        &self.run_values
+    }
+    // In the order they were simulated
+    pub fn get_batting_lines(&self) -> &Vec<BattingLine> {
+        &self.batting_lines
+    }
+    pub fn replications(&self) -> usize {
+        self.obp.len()
+    }
+}
+
+// Simulate every plate appearance of a season once without a bat, in order, so pitchers can
+// adapt as the season goes on
+pub fn simulate_season_no_bat<R: Rng + ?Sized>(
+    rng: &mut R,
+    plate_appearances: &[PlateAppearance],
+    oswing_pct: f32,
+    swing_pct: f32,
+    zone_model: &ZoneModel,
+) -> Vec<PlateAppearance> {
+    plate_appearances
+        .iter()
+        .enumerate()
+        .map(|(index, appearance)| {
+            simulate_plate_appearance_no_bat(
+                rng,
+                appearance,
+                oswing_pct,
+                swing_pct,
+                &zone_model.for_plate_appearance(index, appearance),
+            )
+        })
+        .collect()
+}
+
+// Replay the season `replications` times and collect the spread of the no-bat results. Each
+// simulated plate appearance is scored against `run_expectancy`.
+pub fn replicate_season_no_bat<R: Rng + ?Sized>(
+    rng: &mut R,
+    plate_appearances: &[PlateAppearance],
+    oswing_pct: f32,
+    swing_pct: f32,
+    zone_model: &ZoneModel,
+    run_expectancy: &RunExpectancy,
+    replications: usize,
+) -> ReplicationSummary {
+    replicate_season(rng, run_expectancy, replications, |rng| {
+        simulate_season_no_bat(rng, plate_appearances, oswing_pct, swing_pct, zone_model)
+    })
+}
+
+// Collect the spread of any season replay, e.g. one under a selective swing strategy
+pub fn replicate_season<R, F>(
+    rng: &mut R,
+    run_expectancy: &RunExpectancy,
+    replications: usize,
+    mut simulate_season: F,
+) -> ReplicationSummary
+where
+    R: Rng + ?Sized,
+    F: FnMut(&mut R) -> Vec<PlateAppearance>,
+{
+    let mut obps: Vec<f32> = Vec::with_capacity(replications);
+    let mut walks: Vec<f32> = Vec::with_capacity(replications);
+    let mut strikeouts: Vec<f32> = Vec::with_capacity(replications);
+    let mut run_values: Vec<f32> = Vec::with_capacity(replications);
+    let mut batting_lines: Vec<BattingLine> = Vec::with_capacity(replications);
+    for _ in 0..replications {
+        let season = simulate_season(rng);
+        let line = BattingLine::from_plate_appearances(&season);
+        obps.push(line.on_base_percentage());
+        walks.push(*line.get_walks() as f32);
+        strikeouts.push(*line.get_strikeouts() as f32);
+        run_values.push(run_expectancy.total_run_value(&season) as f32);
+        batting_lines.push(line);
+    }
+    ReplicationSummary::new(
+        Distribution::new(obps),
+        Distribution::new(walks),
+        Distribution::new(strikeouts),
+        Distribution::new(run_values),
+        batting_lines,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn percentiles_interpolate_between_values() {
+        let distribution = Distribution::new(vec![4.0, 1.0, 3.0, 2.0]);
+        let cases = [
+            (0.0, 1.0),
+            (50.0, 2.5),
+            (100.0, 4.0),
+            (25.0, 1.75),
+            (150.0, 4.0),
+        ];
+        for (percent, expected) in cases {
+            assert_eq!(distribution.percentile(percent), expected, "{}", percent);
+        }
+    }
+
+    #[test]
+    fn interval_cuts_equal_tails() {
+        // 0, 1, ..., 200, so the 2.5th percentile lands exactly on 5
+        let distribution = Distribution::new((0..=200).map(|value| value as f32).collect());
+        assert_eq!(distribution.interval(95.0), (5.0, 195.0));
+        assert_eq!(distribution.interval(0.0), (100.0, 100.0));
+    }
+
+    #[test]
+    fn histogram_counts_every_value() {
+        let distribution = Distribution::new((1..=10).map(|value| value as f32).collect());
+        let histogram = distribution.histogram(3);
+        assert_eq!(histogram.len(), 3);
+        assert_eq!(histogram.iter().map(|(_, count)| count).sum::<usize>(), 10);
+        assert_eq!(histogram[0].0, 1.0);
+        // The max sits on the upper edge and falls in the last bin, not past it
+        assert_eq!(histogram[2], (7.0, 4));
+    }
+
+    #[test]
+    fn single_value_has_no_spread() {
+        let distribution = Distribution::new(vec![0.4]);
+        assert_eq!(distribution.mean(), 0.4);
+        assert_eq!(distribution.std_dev(), 0.0);
+        assert_eq!(distribution.interval(95.0), (0.4, 0.4));
+        assert_eq!(distribution.histogram(5)[0], (0.4, 1));
+        assert!(Distribution::new(Vec::new()).percentile(50.0).is_nan());
+    }
+}
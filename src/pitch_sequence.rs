@@ -0,0 +1,256 @@
+use std::fmt;
+
+use crate::codes::PitchCode;
+
+// A pitch delivered to the batter, along with the modifiers written in front of it
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Pitch {
+    code: PitchCode,
+    // `*`, the catcher blocked it
+    blocked: bool,
+    // `>`, a runner was going on it
+    runner_going: bool,
+}
+
+impl Pitch {
+    pub fn new(code: PitchCode, blocked: bool, runner_going: bool) -> Self {
+        Self {
+            code,
+            blocked,
+            runner_going,
+        }
+    }
+    pub fn get_code(&self) -> &PitchCode {
+        &self.code
+    }
+    pub fn is_blocked(&self) -> bool {
+        self.blocked
+    }
+    pub fn is_runner_going(&self) -> bool {
+        self.runner_going
+    }
+    pub fn is_pitchout(&self) -> bool {
+        self.code.is_pitchout()
+    }
+}
+
+impl fmt::Display for Pitch {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.blocked {
+            write!(f, "*")?;
+        }
+        if self.runner_going {
+            write!(f, ">")?;
+        }
+        write!(f, "{}", self.code)
+    }
+}
+
+// One entry of a pitch sequence: a pitch or something that happened between pitches
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum SequenceEvent {
+    Pitch(Pitch),
+    // Throw to first, second or third, `+` in front when the catcher made it
+    Pickoff { base: u8, by_catcher: bool },
+    // `N`, a balk or interference call that did not count as a pitch
+    NoPitch,
+    // `.`, a play not involving the batter, with its event when the file has the record for it
+    Play(Option<String>),
+}
+
+impl fmt::Display for SequenceEvent {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            SequenceEvent::Pitch(pitch) => write!(f, "{}", pitch),
+            SequenceEvent::Pickoff { base, by_catcher } => {
+                write!(f, "{}{}", if *by_catcher { "+" } else { "" }, base)
+            }
+            SequenceEvent::NoPitch => write!(f, "N"),
+            SequenceEvent::Play(Some(event)) => write!(f, "[{}]", event),
+            SequenceEvent::Play(None) => write!(f, "."),
+        }
+    }
+}
+
+// A plate appearance's pitch sequence as Retrosheet recorded it, with modifiers attached to the
+// pitch or throw they belong to
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct PitchSequence {
+    events: Vec<SequenceEvent>,
+}
+
+impl PitchSequence {
+    // Parse a raw sequence like `B1>C.BX`, skipping any character Retrosheet does not define.
+    // A modifier with nothing after it is dropped.
+    pub fn parse(sequence: &str) -> Self {
+        let mut events = Vec::new();
+        let mut blocked = false;
+        let mut runner_going = false;
+        let mut by_catcher = false;
+        for code in sequence.chars().filter_map(PitchCode::from_char) {
+            let event = match code {
+                PitchCode::BlockedByCatcher => {
+                    blocked = true;
+                    continue;
+                }
+                PitchCode::RunnerGoing => {
+                    runner_going = true;
+                    continue;
+                }
+                PitchCode::CatcherPickoffThrow => {
+                    by_catcher = true;
+                    continue;
+                }
+                PitchCode::PickoffThrow(base) => SequenceEvent::Pickoff {
+                    base,
+                    by_catcher: std::mem::take(&mut by_catcher),
+                },
+                PitchCode::NonBatterPlay => SequenceEvent::Play(None),
+                PitchCode::NoPitch => SequenceEvent::NoPitch,
+                code => SequenceEvent::Pitch(Pitch::new(
+                    code,
+                    std::mem::take(&mut blocked),
+                    std::mem::take(&mut runner_going),
+                )),
+            };
+            events.push(event);
+        }
+        Self { events }
+    }
+
+    // Fill in the plays not involving the batter, in the order they happened
+    pub fn with_plays(mut self, plays: impl IntoIterator<Item = String>) -> Self {
+        let mut plays = plays.into_iter();
+        for event in &mut self.events {
+            if let SequenceEvent::Play(play) = event {
+                match plays.next() {
+                    Some(next) => *play = Some(next),
+                    None => break,
+                }
+            }
+        }
+        self
+    }
+
+    pub fn get_events(&self) -> &Vec<SequenceEvent> {
+        &self.events
+    }
+
+    // Only the pitches that were actually delivered to the batter
+    pub fn pitches(&self) -> impl Iterator<Item = &Pitch> {
+        self.events.iter().filter_map(|event| match event {
+            SequenceEvent::Pitch(pitch) => Some(pitch),
+            _ => None,
+        })
+    }
+
+    // Event of each play not involving the batter in order, None where the file had no record
+    // of it
+    pub fn plays(&self) -> impl Iterator<Item = Option<&String>> {
+        self.events.iter().filter_map(|event| match event {
+            SequenceEvent::Play(play) => Some(play.as_ref()),
+            _ => None,
+        })
+    }
+}
+
+impl fmt::Display for PitchSequence {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let events = self
+            .events
+            .iter()
+            .map(SequenceEvent::to_string)
+            .collect::<Vec<String>>();
+        write!(f, "{}", events.join(" "))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_modifiers_onto_what_follows() {
+        // Raw sequence and its events as displayed, one per space
+        let cases = [
+            ("BCFX", "B C F X"),
+            ("*BX", "*B X"),
+            (">B>C", ">B >C"),
+            ("*>B", "*>B"),
+            ("B1>C", "B 1 >C"),
+            ("+2B", "+2 B"),
+            ("1+1C", "1 +1 C"),
+            ("PQRY", "P Q R Y"),
+            ("B.CX", "B . C X"),
+            ("N", "N"),
+            ("BB>", "B B"),
+            ("B?C", "B C"),
+            ("", ""),
+        ];
+        for (raw, expected) in cases {
+            assert_eq!(PitchSequence::parse(raw).to_string(), expected, "{}", raw);
+        }
+    }
+
+    #[test]
+    fn parses_pickoffs_and_pitchouts() {
+        let sequence = PitchSequence::parse("+3*P1Q");
+        assert_eq!(
+            sequence.get_events(),
+            &vec![
+                SequenceEvent::Pickoff {
+                    base: 3,
+                    by_catcher: true
+                },
+                SequenceEvent::Pitch(Pitch::new(PitchCode::Pitchout, true, false)),
+                SequenceEvent::Pickoff {
+                    base: 1,
+                    by_catcher: false
+                },
+                SequenceEvent::Pitch(Pitch::new(PitchCode::SwingingOnPitchout, false, false)),
+            ]
+        );
+        let pitchouts = sequence
+            .pitches()
+            .map(Pitch::is_pitchout)
+            .collect::<Vec<bool>>();
+        assert_eq!(pitchouts, [true, true]);
+    }
+
+    #[test]
+    fn pitches_leave_out_throws_and_plays() {
+        let sequence = PitchSequence::parse("B1>C.N+2*BX");
+        let pitches = sequence
+            .pitches()
+            .map(|pitch| pitch.to_string())
+            .collect::<Vec<String>>();
+        assert_eq!(pitches, ["B", ">C", "*B", "X"]);
+    }
+
+    #[test]
+    fn plays_line_up_with_the_dots() {
+        let plays = |raw: &str, events: &[&str]| {
+            PitchSequence::parse(raw)
+                .with_plays(events.iter().map(|event| event.to_string()))
+                .plays()
+                .map(|play| play.cloned())
+                .collect::<Vec<Option<String>>>()
+        };
+        let some = |event: &str| Some(event.to_owned());
+        assert_eq!(
+            plays("B.C.X", &["SB2", "WP.2-3"]),
+            [some("SB2"), some("WP.2-3")]
+        );
+        // The file had a record for only the first play
+        assert_eq!(plays("B.C.X", &["SB2"]), [some("SB2"), None]);
+        // Records with no dot left for them are dropped
+        assert_eq!(plays("B.CX", &["SB2", "WP"]), [some("SB2")]);
+        assert_eq!(plays("BCX", &["SB2"]), Vec::<Option<String>>::new());
+        assert_eq!(
+            PitchSequence::parse("B.C")
+                .with_plays(["SB2".to_owned()])
+                .to_string(),
+            "B [SB2] C"
+        );
+    }
+}
@@ -0,0 +1,275 @@
+use crate::roster::{Hand, RosterEntry};
+use crate::zone_model::taken_pitch_counts;
+use crate::{PitchCode, PlateAppearance};
+
+fn hand_index(hand: Hand) -> usize {
+    match hand {
+        Hand::Left => 0,
+        Hand::Right => 1,
+    }
+}
+
+// League called strike rate on taken pitches for each matchup of batter and pitcher hands.
+// Pitchers work away from same-handed hitters less, so lefty-lefty sees the most strikes.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct PlatoonZoneRates {
+    // Called strikes and taken pitches, indexed [batter][pitcher]
+    taken: [[(u32, u32); 2]; 2],
+}
+
+impl PlatoonZoneRates {
+    pub fn from_plate_appearances<'a>(
+        plate_appearances: impl IntoIterator<Item = &'a PlateAppearance>,
+    ) -> Self {
+        let mut taken = [[(0, 0); 2]; 2];
+        for appearance in plate_appearances {
+            let (batter, pitcher) =
+                match (appearance.get_batter_hand(), appearance.get_pitcher_hand()) {
+                    (Some(batter), Some(pitcher)) => (*batter, *pitcher),
+                    _ => continue,
+                };
+            let (called_strikes, samples) = taken_pitch_counts(appearance);
+            let taken = &mut taken[hand_index(batter)][hand_index(pitcher)];
+            taken.0 += called_strikes;
+            taken.1 += samples;
+        }
+        Self { taken }
+    }
+
+    pub fn get_samples(&self, batter: Hand, pitcher: Hand) -> u32 {
+        self.taken[hand_index(batter)][hand_index(pitcher)].1
+    }
+
+    // Percent of taken pitches called strikes in the matchup
+    pub fn strike_pct(&self, batter: Hand, pitcher: Hand) -> f32 {
+        let (called_strikes, samples) = self.taken[hand_index(batter)][hand_index(pitcher)];
+        if samples == 0 {
+            return self.overall_pct();
+        }
+        100.0 * called_strikes as f32 / samples as f32
+    }
+
+    pub fn overall_pct(&self) -> f32 {
+        let (called_strikes, samples) = self
+            .taken
+            .iter()
+            .flatten()
+            .fold((0, 0), |(strikes, total), (s, n)| (strikes + s, total + n));
+        if samples == 0 {
+            return 0.0;
+        }
+        100.0 * called_strikes as f32 / samples as f32
+    }
+
+    // How many more strikes than average the matchup sees, e.g. 1.03 for 3% more
+    pub fn factor(&self, batter: Hand, pitcher: Hand) -> f32 {
+        let overall_pct = self.overall_pct();
+        if overall_pct > 0.0 {
+            self.strike_pct(batter, pitcher) / overall_pct
+        } else {
+            1.0
+        }
+    }
+}
+
+// Pitches a batter's swing rate against one hand is shrunk toward his overall rate by, so a
+// handful of plate appearances against lefties does not swing it far
+const SWING_PRIOR_PITCHES: f32 = 100.0;
+
+// How much more or less often the batter swung at pitches from left- and right-handed pitchers
+// than at all of them, normalized so the factors average to 1 over the pitches he saw from each
+// hand
+fn swing_factors(plate_appearances: &[PlateAppearance]) -> [f32; 2] {
+    // Swings and pitches he could have offered at, indexed by the pitcher's hand
+    let mut seen = [(0, 0); 2];
+    for appearance in plate_appearances {
+        let pitcher = match appearance.get_pitcher_hand() {
+            Some(pitcher) => *pitcher,
+            None => continue,
+        };
+        let (swings, pitches) = &mut seen[hand_index(pitcher)];
+        for pitch in appearance.get_pitches() {
+            if !pitch.is_pitch()
+                || matches!(
+                    pitch,
+                    PitchCode::IntentionalBall
+                        | PitchCode::StrikeUnknownType
+                        | PitchCode::Unknown
+                        | PitchCode::AutomaticBall
+                        | PitchCode::AutomaticStrike
+                )
+            {
+                continue;
+            }
+            *pitches += 1;
+            if pitch.is_swing() {
+                *swings += 1;
+            }
+        }
+    }
+    let total_pitches = (seen[0].1 + seen[1].1) as f32;
+    if total_pitches == 0.0 {
+        return [1.0, 1.0];
+    }
+    let overall = (seen[0].0 + seen[1].0) as f32 / total_pitches;
+    if overall == 0.0 {
+        return [1.0, 1.0];
+    }
+    let factors = seen.map(|(swings, pitches)| {
+        (swings as f32 + SWING_PRIOR_PITCHES * overall)
+            / (pitches as f32 + SWING_PRIOR_PITCHES)
+            / overall
+    });
+    let average = (factors[0] * seen[0].1 as f32 + factors[1] * seen[1].1 as f32) / total_pitches;
+    factors.map(|factor| factor / average)
+}
+
+// A batter's O-Swing%, Swing% and Zone% against left- and right-handed pitchers, in the same
+// order as `PlateDiscipline::simulation_rates`
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct PlatoonSplits {
+    vs_left: (f32, f32, f32),
+    vs_right: (f32, f32, f32),
+}
+
+impl PlatoonSplits {
+    pub fn new(vs_left: (f32, f32, f32), vs_right: (f32, f32, f32)) -> Self {
+        Self { vs_left, vs_right }
+    }
+
+    // Without split tables the batter's Zone% is scaled by how much more or less the league's
+    // hitters on his side see strikes from that hand, and his O-Swing% and Swing% by how much
+    // more or less often he offered at pitches from that hand in `plate_appearances`. The swing
+    // rates are scaled so that, weighted by the pitches he saw from each hand, they average back
+    // to his overall ones.
+    pub fn estimate(
+        rates: (f32, f32, f32),
+        entry: &RosterEntry,
+        league: &PlatoonZoneRates,
+        plate_appearances: &[PlateAppearance],
+    ) -> Self {
+        let (oswing_pct, swing_pct, zone_pct) = rates;
+        let swing_factors = swing_factors(plate_appearances);
+        let against = |pitcher: Hand| {
+            let swing_factor = swing_factors[hand_index(pitcher)];
+            let zone_pct = match entry.batting_hand(Some(pitcher)) {
+                Some(batter) => (zone_pct * league.factor(batter, pitcher)).min(100.0),
+                None => zone_pct,
+            };
+            (
+                (oswing_pct * swing_factor).min(100.0),
+                (swing_pct * swing_factor).min(100.0),
+                zone_pct,
+            )
+        };
+        Self::new(against(Hand::Left), against(Hand::Right))
+    }
+
+    pub fn get(&self, pitcher: Hand) -> &(f32, f32, f32) {
+        match pitcher {
+            Hand::Left => &self.vs_left,
+            Hand::Right => &self.vs_right,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::PlateOutcome;
+
+    fn appearance(pitcher: Hand, pitches: &str) -> PlateAppearance {
+        PlateAppearance::fixture(PlateOutcome::Walk, pitches).with_pitcher_hand(pitcher)
+    }
+
+    fn bonds() -> RosterEntry {
+        RosterEntry::new(
+            "bondb001".to_owned(),
+            "Bonds".to_owned(),
+            "Barry".to_owned(),
+            "L".to_owned(),
+            "L".to_owned(),
+            "SFN".to_owned(),
+            "OF".to_owned(),
+        )
+    }
+
+    // Swings far more often against lefties, who he saw fewer pitches from
+    fn plate_appearances() -> Vec<PlateAppearance> {
+        let mut plate_appearances = Vec::new();
+        for _ in 0..30 {
+            plate_appearances.push(appearance(Hand::Left, "SFX"));
+            plate_appearances.push(appearance(Hand::Right, "BBCBB"));
+            plate_appearances.push(appearance(Hand::Right, "BSFBX"));
+        }
+        plate_appearances
+    }
+
+    #[test]
+    fn swing_rates_split_by_pitcher_hand() {
+        let rates = (25.0, 40.0, 45.0);
+        let splits = PlatoonSplits::estimate(
+            rates,
+            &bonds(),
+            &PlatoonZoneRates::default(),
+            &plate_appearances(),
+        );
+        let (left_oswing, left_swing, _) = *splits.get(Hand::Left);
+        let (right_oswing, right_swing, _) = *splits.get(Hand::Right);
+        assert!(left_swing > 40.0 && right_swing < 40.0);
+        assert!((left_oswing / left_swing - 25.0 / 40.0).abs() < 1e-5);
+        assert!((right_oswing / right_swing - 25.0 / 40.0).abs() < 1e-5);
+    }
+
+    #[test]
+    fn swing_rates_average_back_to_overall() {
+        let plate_appearances = plate_appearances();
+        let splits = PlatoonSplits::estimate(
+            (25.0, 40.0, 45.0),
+            &bonds(),
+            &PlatoonZoneRates::default(),
+            &plate_appearances,
+        );
+        // 90 pitches from lefties, 300 from righties
+        let average = (90.0 * splits.get(Hand::Left).1 + 300.0 * splits.get(Hand::Right).1) / 390.0;
+        assert!((average - 40.0).abs() < 1e-3, "{}", average);
+    }
+
+    #[test]
+    fn no_pitches_keeps_overall_swing_rates() {
+        let splits = PlatoonSplits::estimate(
+            (25.0, 40.0, 45.0),
+            &bonds(),
+            &PlatoonZoneRates::default(),
+            &[],
+        );
+        assert_eq!(splits.get(Hand::Left).1, 40.0);
+        assert_eq!(splits.get(Hand::Right).1, 40.0);
+    }
+
+    #[test]
+    fn zone_rates_count_taken_pitches_by_matchup() {
+        let matchup = |batter: Hand, pitcher: Hand, pitches: &str| {
+            appearance(pitcher, pitches).with_batter_hand(batter)
+        };
+        let plate_appearances = [
+            matchup(Hand::Left, Hand::Left, "CCBBBB"),
+            matchup(Hand::Left, Hand::Right, "CBBBB"),
+            matchup(Hand::Right, Hand::Right, "BBBB"),
+            // Four balls recorded as an 0-2 count, left out of every matchup
+            matchup(Hand::Right, Hand::Right, "CCCC").with_count(0, 2),
+            // No batter hand to put it under
+            appearance(Hand::Left, "CCC"),
+        ];
+        let rates = PlatoonZoneRates::from_plate_appearances(&plate_appearances);
+        assert_eq!(rates.get_samples(Hand::Left, Hand::Left), 6);
+        assert_eq!(rates.get_samples(Hand::Right, Hand::Right), 4);
+        assert_eq!(rates.get_samples(Hand::Right, Hand::Left), 0);
+        assert!((rates.overall_pct() - 100.0 * 3.0 / 15.0).abs() < 1e-4);
+        assert!((rates.strike_pct(Hand::Left, Hand::Left) - 100.0 * 2.0 / 6.0).abs() < 1e-4);
+        assert_eq!(rates.strike_pct(Hand::Right, Hand::Right), 0.0);
+        // A matchup nobody saw is average
+        assert_eq!(rates.factor(Hand::Right, Hand::Left), 1.0);
+        assert!((rates.factor(Hand::Left, Hand::Left) - 2.0 / 6.0 / 0.2).abs() < 1e-4);
+    }
+}
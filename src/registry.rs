@@ -0,0 +1,384 @@
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+
+use crate::discipline::split_fields;
+use crate::error::{Error, Result};
+use crate::roster::RosterEntry;
+use crate::season::PlayerId;
+
+// Chadwick Bureau register file in the data directory, see
+// https://github.com/chadwickbureau/register
+pub static CROSSWALK_FILE: &str = "people.csv";
+
+// Generational suffixes dropped when comparing names
+const NAME_SUFFIXES: [&str; 5] = ["jr", "sr", "ii", "iii", "iv"];
+
+// ASCII spelling of the accented letters that show up in player names
+fn fold_accent(c: char) -> Option<&'static str> {
+    let folded = match c {
+        'à' | 'á' | 'â' | 'ã' | 'ä' | 'å' | 'ā' | 'ă' | 'ą' => "a",
+        'æ' => "ae",
+        'ç' | 'ć' | 'č' => "c",
+        'ď' | 'đ' => "d",
+        'è' | 'é' | 'ê' | 'ë' | 'ē' | 'ė' | 'ę' | 'ě' => "e",
+        'ì' | 'í' | 'î' | 'ï' | 'ī' | 'į' => "i",
+        'ł' => "l",
+        'ñ' | 'ń' | 'ň' => "n",
+        'ò' | 'ó' | 'ô' | 'õ' | 'ö' | 'ø' | 'ō' | 'ő' => "o",
+        'œ' => "oe",
+        'ř' => "r",
+        'ś' | 'š' | 'ş' => "s",
+        'ß' => "ss",
+        'ť' | 'ţ' => "t",
+        'ù' | 'ú' | 'û' | 'ü' | 'ū' | 'ů' | 'ű' => "u",
+        'ý' | 'ÿ' => "y",
+        'ź' | 'ż' | 'ž' => "z",
+        _ => return None,
+    };
+    Some(folded)
+}
+
+// Comparable form of a player name: lowercase ASCII, no punctuation or suffix, single spaces.
+// "Adolis García" and "Adolis Garcia" both become "adolis garcia", "J.T. Snow" becomes
+// "jt snow" and "Fernando Tatis Jr." becomes "fernando tatis".
+pub fn normalize_name(name: &str) -> String {
+    let mut folded = String::with_capacity(name.len());
+    for c in name.chars().flat_map(|c| c.to_lowercase()) {
+        if let Some(ascii) = fold_accent(c) {
+            folded.push_str(ascii);
+        } else if c.is_ascii_alphanumeric() {
+            folded.push(c);
+        } else if c.is_whitespace() || c == '-' || c == ',' {
+            folded.push(' ');
+        }
+        // Periods and apostrophes are dropped so "J.T." and "JT" or "O'Neill" and "ONeill" agree
+    }
+    let words = folded
+        .split_whitespace()
+        .filter(|word| !NAME_SUFFIXES.contains(word))
+        .collect::<Vec<&str>>();
+    words.join(" ")
+}
+
+// One person from the crosswalk file
+#[derive(Clone, Debug)]
+pub struct CrosswalkEntry {
+    retrosheet_id: PlayerId,
+    fangraphs_id: Option<String>,
+    first_name: String,
+    last_name: String,
+}
+
+impl CrosswalkEntry {
+    pub fn new(
+        retrosheet_id: PlayerId,
+        fangraphs_id: Option<String>,
+        first_name: String,
+        last_name: String,
+    ) -> Self {
+        Self {
+            retrosheet_id,
+            fangraphs_id,
+            first_name,
+            last_name,
+        }
+    }
+    pub fn get_retrosheet_id(&self) -> &PlayerId {
+        &self.retrosheet_id
+    }
+    pub fn get_fangraphs_id(&self) -> &Option<String> {
+        &self.fangraphs_id
+    }
+    pub fn get_first_name(&self) -> &String {
+        &self.first_name
+    }
+    pub fn get_last_name(&self) -> &String {
+        &self.last_name
+    }
+    pub fn get_full_name(&self) -> String {
+        format!("{} {}", self.first_name, self.last_name)
+    }
+}
+
+// Parse a Chadwick register style file. Only the `key_retro`, `key_fangraphs`, `name_first` and
+// `name_last` columns are read; people without a Retrosheet ID are skipped.
+pub fn parse_crosswalk(contents: &str, file: &str) -> Result<Vec<CrosswalkEntry>> {
+    let mut lines = contents.lines().enumerate();
+    let header = match lines.next() {
+        Some((_, line)) => split_fields(line.trim_start_matches('\u{feff}'), ','),
+        None => return Ok(Vec::new()),
+    };
+    let column = |name: &str| header.iter().position(|field| field == name);
+    let retro_column = column("key_retro")
+        .ok_or_else(|| Error::malformed(file, 1, "crosswalk has no key_retro column"))?;
+    let fangraphs_column = column("key_fangraphs")
+        .ok_or_else(|| Error::malformed(file, 1, "crosswalk has no key_fangraphs column"))?;
+    let first_column = column("name_first");
+    let last_column = column("name_last");
+
+    let mut crosswalk: Vec<CrosswalkEntry> = Vec::new();
+    for (index, line) in lines {
+        let fields = split_fields(line.trim_end(), ',');
+        let field = |column: Option<usize>| {
+            column
+                .and_then(|column| fields.get(column))
+                .cloned()
+                .unwrap_or_default()
+        };
+        let retrosheet_id = field(Some(retro_column));
+        if retrosheet_id.is_empty() {
+            continue;
+        }
+        if fields.len() < header.len() {
+            return Err(Error::malformed(
+                file,
+                index + 1,
+                format!("expected {} columns, found {}", header.len(), fields.len()),
+            ));
+        }
+        let fangraphs_id = Some(field(Some(fangraphs_column))).filter(|id| !id.is_empty());
+        crosswalk.push(CrosswalkEntry::new(
+            retrosheet_id,
+            fangraphs_id,
+            field(first_column),
+            field(last_column),
+        ));
+    }
+    Ok(crosswalk)
+}
+
+pub fn read_crosswalk_from_file(path: &Path) -> Result<Vec<CrosswalkEntry>> {
+    let contents = fs::read_to_string(path).map_err(|e| Error::io(path, e))?;
+    parse_crosswalk(&contents, &path.display().to_string())
+}
+
+// Every player on the season's rosters, found by Retrosheet ID or by normalized name
+#[derive(Clone, Debug, Default)]
+pub struct PlayerRegistry {
+    entries: HashMap<PlayerId, Vec<RosterEntry>>,
+    names: HashMap<String, Vec<PlayerId>>,
+    fangraphs_ids: HashMap<PlayerId, String>,
+    // Names from the crosswalk that differ from the roster's, e.g. "Mike" for "Michael"
+    aliases: HashMap<PlayerId, Vec<String>>,
+}
+
+impl PlayerRegistry {
+    pub fn new(roster: &[RosterEntry]) -> Self {
+        let mut registry = PlayerRegistry::default();
+        for entry in roster {
+            registry.add_name(entry.get_id(), &entry.get_full_name());
+            registry
+                .entries
+                .entry(entry.get_id().clone())
+                .or_default()
+                .push(entry.clone());
+        }
+        registry
+    }
+
+    fn add_name(&mut self, player_id: &PlayerId, name: &str) {
+        let ids = self.names.entry(normalize_name(name)).or_default();
+        if !ids.contains(player_id) {
+            ids.push(player_id.clone());
+        }
+    }
+
+    // Attach FanGraphs IDs and alternate names from a crosswalk. People who are not on any of
+    // the season's rosters are ignored.
+    pub fn add_crosswalk(&mut self, crosswalk: &[CrosswalkEntry]) {
+        for person in crosswalk {
+            let player_id = person.get_retrosheet_id();
+            if !self.entries.contains_key(player_id) {
+                continue;
+            }
+            if let Some(fangraphs_id) = person.get_fangraphs_id() {
+                self.fangraphs_ids
+                    .insert(player_id.clone(), fangraphs_id.clone());
+            }
+            let name = person.get_full_name();
+            if !self
+                .get_names(player_id)
+                .iter()
+                .any(|known| normalize_name(known) == normalize_name(&name))
+            {
+                self.add_name(player_id, &name);
+                self.aliases
+                    .entry(player_id.clone())
+                    .or_default()
+                    .push(name);
+            }
+        }
+    }
+
+    pub fn contains(&self, player_id: &str) -> bool {
+        self.entries.contains_key(player_id)
+    }
+
+    // The player's roster lines, one per team he was listed on
+    pub fn get_roster_entries(&self, player_id: &str) -> &[RosterEntry] {
+        self.entries
+            .get(player_id)
+            .map(|entries| entries.as_slice())
+            .unwrap_or(&[])
+    }
+
+    pub fn get_fangraphs_id(&self, player_id: &str) -> Option<&String> {
+        self.fangraphs_ids.get(player_id)
+    }
+
+    // Every name the player goes by, roster spelling first
+    pub fn get_names(&self, player_id: &str) -> Vec<String> {
+        let mut names: Vec<String> = Vec::new();
+        for name in self
+            .get_roster_entries(player_id)
+            .iter()
+            .map(|entry| entry.get_full_name())
+            .chain(self.aliases.get(player_id).cloned().unwrap_or_default())
+        {
+            if !names.contains(&name) {
+                names.push(name);
+            }
+        }
+        names
+    }
+
+    // Retrosheet IDs of every player going by the name, in roster order
+    pub fn find_by_name(&self, player_name: &str) -> &[PlayerId] {
+        self.names
+            .get(&normalize_name(player_name))
+            .map(|ids| ids.as_slice())
+            .unwrap_or(&[])
+    }
+
+    // Resolve a name or Retrosheet ID to a single player. `team_name` narrows the search to the
+    // players who were on that team's roster at some point in the season.
+    pub fn find_player_id(
+        &self,
+        player_name: &str,
+        team_name: Option<&str>,
+        year: i32,
+    ) -> Result<PlayerId> {
+        if self.contains(player_name) {
+            return Ok(player_name.to_owned());
+        }
+        let mut player_ids = self
+            .find_by_name(player_name)
+            .iter()
+            .filter(|id| {
+                team_name.is_none_or(|team_name| {
+                    self.get_roster_entries(id)
+                        .iter()
+                        .any(|entry| entry.get_team() == team_name)
+                })
+            })
+            .cloned()
+            .collect::<Vec<PlayerId>>();
+        match player_ids.len() {
+            0 => Err(Error::UnknownPlayer {
+                name: player_name.to_owned(),
+                team: team_name.map(str::to_owned),
+                year,
+            }),
+            1 => Ok(player_ids.remove(0)),
+            _ => Err(Error::AmbiguousName {
+                name: player_name.to_owned(),
+                candidates: player_ids,
+            }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(id: &str, first_name: &str, last_name: &str, team: &str) -> RosterEntry {
+        RosterEntry::new(
+            id.to_owned(),
+            last_name.to_owned(),
+            first_name.to_owned(),
+            "R".to_owned(),
+            "R".to_owned(),
+            team.to_owned(),
+            "OF".to_owned(),
+        )
+    }
+
+    #[test]
+    fn normalizes_names() {
+        let cases = [
+            ("Adolis García", "adolis garcia"),
+            ("Fernando Tatis Jr.", "fernando tatis"),
+            ("Ken Griffey, Jr.", "ken griffey"),
+            ("J.T. Snow", "jt snow"),
+            ("JT Snow", "jt snow"),
+            ("Paul O'Neill", "paul oneill"),
+            ("Jean-Pierre  Roy", "jean pierre roy"),
+            ("Cal Ripken III", "cal ripken"),
+        ];
+        for (name, expected) in cases {
+            assert_eq!(normalize_name(name), expected, "{}", name);
+        }
+    }
+
+    #[test]
+    fn same_normalized_name_needs_a_team() {
+        let registry = PlayerRegistry::new(&[
+            entry("smitb001", "Bobby", "Smith", "SFN"),
+            entry("smitb002", "Bobby", "Smith Jr.", "MIL"),
+            entry("snowj001", "J.T.", "Snow", "SFN"),
+        ]);
+        assert!(matches!(
+            registry.find_player_id("Bobby Smith", None, 2004),
+            Err(Error::AmbiguousName { candidates, .. }) if candidates == ["smitb001", "smitb002"]
+        ));
+        let cases = [
+            ("Bobby Smith", Some("SFN"), "smitb001"),
+            ("bobby smith jr", Some("MIL"), "smitb002"),
+            ("JT Snow", None, "snowj001"),
+            ("snowj001", None, "snowj001"),
+        ];
+        for (name, team, expected) in cases {
+            assert_eq!(registry.find_player_id(name, team, 2004).unwrap(), expected);
+        }
+        assert!(matches!(
+            registry.find_player_id("Bobby Smith", Some("CHN"), 2004),
+            Err(Error::UnknownPlayer { .. })
+        ));
+    }
+
+    #[test]
+    fn crosswalk_rows_may_lack_a_fangraphs_id() {
+        let contents = "key_person,key_retro,key_fangraphs,name_last,name_first\n\
+                        a1,bondb001,1109,Bonds,Barry\n\
+                        a2,snowj001,,Snow,Jack\n\
+                        a3,,2000,Nobody,Retro\n";
+        let crosswalk = parse_crosswalk(contents, "people.csv").unwrap();
+        assert_eq!(crosswalk.len(), 2);
+        assert_eq!(crosswalk[0].get_fangraphs_id(), &Some("1109".to_owned()));
+        assert_eq!(crosswalk[1].get_fangraphs_id(), &None);
+
+        let mut registry = PlayerRegistry::new(&[
+            entry("bondb001", "Barry", "Bonds", "SFN"),
+            entry("snowj001", "J.T.", "Snow", "SFN"),
+        ]);
+        registry.add_crosswalk(&crosswalk);
+        assert_eq!(
+            registry.get_fangraphs_id("bondb001"),
+            Some(&"1109".to_owned())
+        );
+        assert_eq!(registry.get_fangraphs_id("snowj001"), None);
+        // The crosswalk's first name works as well as the roster's
+        assert_eq!(
+            registry.find_player_id("Jack Snow", None, 2004).unwrap(),
+            "snowj001"
+        );
+    }
+
+    #[test]
+    fn crosswalk_without_key_columns_is_an_error() {
+        assert!(parse_crosswalk("key_retro,name_first\nbondb001,Barry\n", "people.csv").is_err());
+        assert!(parse_crosswalk("key_retro,key_fangraphs\nbondb001\n", "people.csv").is_err());
+    }
+}
@@ -0,0 +1,206 @@
+use std::path::Path;
+
+use crate::error::{Error, Result};
+use crate::registry::normalize_name;
+use crate::{list_season_files, season_directory};
+
+// Which side a batter hits from or a pitcher throws with
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Hand {
+    Left,
+    Right,
+}
+
+impl Hand {
+    // `L` or `R`. Switch hitters (`B`) have no hand of their own until they face a pitcher.
+    pub fn from_code(code: &str) -> Option<Hand> {
+        match code {
+            "L" => Some(Hand::Left),
+            "R" => Some(Hand::Right),
+            _ => None,
+        }
+    }
+    pub fn opposite(&self) -> Hand {
+        match self {
+            Hand::Left => Hand::Right,
+            Hand::Right => Hand::Left,
+        }
+    }
+    pub fn to_code(&self) -> &'static str {
+        match self {
+            Hand::Left => "L",
+            Hand::Right => "R",
+        }
+    }
+}
+
+// One line of a Retrosheet .ROS file, e.g. `bondb001,Bonds,Barry,L,L,SFN,OF`
+#[derive(Clone, Debug)]
+pub struct RosterEntry {
+    id: String,
+    last_name: String,
+    first_name: String,
+    bats: String,
+    throws: String,
+    team: String,
+    position: String,
+}
+
+impl RosterEntry {
+    pub fn new(
+        id: String,
+        last_name: String,
+        first_name: String,
+        bats: String,
+        throws: String,
+        team: String,
+        position: String,
+    ) -> Self {
+        Self {
+            id,
+            last_name,
+            first_name,
+            bats,
+            throws,
+            team,
+            position,
+        }
+    }
+    pub fn get_id(&self) -> &String {
+        &self.id
+    }
+    pub fn get_last_name(&self) -> &String {
+        &self.last_name
+    }
+    pub fn get_first_name(&self) -> &String {
+        &self.first_name
+    }
+    pub fn get_bats(&self) -> &String {
+        &self.bats
+    }
+    pub fn get_throws(&self) -> &String {
+        &self.throws
+    }
+    pub fn get_team(&self) -> &String {
+        &self.team
+    }
+    // The side he hits from against a pitcher throwing with `pitcher`, switch hitters taking the
+    // opposite one
+    pub fn batting_hand(&self, pitcher: Option<Hand>) -> Option<Hand> {
+        match self.bats.as_str() {
+            "B" => pitcher.map(|hand| hand.opposite()),
+            bats => Hand::from_code(bats),
+        }
+    }
+    pub fn throwing_hand(&self) -> Option<Hand> {
+        Hand::from_code(&self.throws)
+    }
+    pub fn get_position(&self) -> &String {
+        &self.position
+    }
+    // First and last name as FanGraphs writes it
+    pub fn get_full_name(&self) -> String {
+        format!("{} {}", self.first_name, self.last_name)
+    }
+}
+
+// Parse a team's .ROS file. `team_name` is used for lines that leave the team column out.
+pub fn parse_roster(contents: &str, team_name: &str) -> Vec<RosterEntry> {
+    let mut roster: Vec<RosterEntry> = Vec::new();
+    for line in contents.lines() {
+        let line_data = line.trim_end().split(',').collect::<Vec<&str>>();
+        if line_data.len() < 3 {
+            continue;
+        }
+        let column = |index: usize| line_data.get(index).copied().unwrap_or("").to_owned();
+        let team = match line_data.get(5) {
+            Some(team) if !team.is_empty() => team.to_string(),
+            _ => team_name.to_owned(),
+        };
+        roster.push(RosterEntry::new(
+            column(0),
+            column(1),
+            column(2),
+            column(3),
+            column(4),
+            team,
+            column(6),
+        ));
+    }
+    roster
+}
+
+// Read every .ROS file of the season
+pub fn read_season_rosters(data_dir: &Path, year: i32) -> Result<Vec<RosterEntry>> {
+    let mut roster: Vec<RosterEntry> = Vec::new();
+    for file in list_season_files(data_dir, year)? {
+        if file.extension() != Some("ROS") {
+            continue;
+        }
+        // Roster files are named like SFN2004.ROS
+        let team_name = file.file_name().get(..3).unwrap_or_default();
+        roster.extend(parse_roster(&file.read_to_string()?, team_name));
+    }
+    Ok(roster)
+}
+
+// Find the player's Retrosheet ID among roster entries for his team
+pub fn find_player_id_in_roster(
+    roster: &[RosterEntry],
+    player_name: &str,
+    team_name: &str,
+    year: i32,
+) -> Result<String> {
+    let normalized_name = normalize_name(player_name);
+    let mut player_ids = roster
+        .iter()
+        .filter(|entry| {
+            entry.team == team_name && normalize_name(&entry.get_full_name()) == normalized_name
+        })
+        .map(|entry| entry.id.clone())
+        .collect::<Vec<String>>();
+    match player_ids.len() {
+        0 => Err(Error::UnknownPlayer {
+            name: player_name.to_owned(),
+            team: Some(team_name.to_owned()),
+            year,
+        }),
+        1 => Ok(player_ids.remove(0)),
+        _ => Err(Error::AmbiguousName {
+            name: player_name.to_owned(),
+            candidates: player_ids,
+        }),
+    }
+}
+
+// Find the player's Retrosheet ID in his team's .ROS file
+pub fn find_player_id(
+    data_dir: &Path,
+    player_name: &str,
+    team_name: &str,
+    year: i32,
+) -> Result<String> {
+    let file_name = format!("{team_name}{year}.ROS");
+    let roster = match list_season_files(data_dir, year)?
+        .into_iter()
+        .find(|file| file.file_name() == file_name)
+    {
+        Some(file) => parse_roster(&file.read_to_string()?, team_name),
+        None => {
+            let path_in_roster = season_directory(data_dir, year).join(&file_name);
+            return Err(Error::io(
+                path_in_roster,
+                std::io::Error::from(std::io::ErrorKind::NotFound),
+            ));
+        }
+    };
+    find_player_id_in_roster(&roster, player_name, team_name, year)
+}
+
+// Every (player name, team) pair from the season's rosters
+pub fn read_all_player_names(data_dir: &Path, year: i32) -> Result<Vec<(String, String)>> {
+    Ok(read_season_rosters(data_dir, year)?
+        .iter()
+        .map(|entry| (entry.get_full_name(), entry.team.clone()))
+        .collect())
+}
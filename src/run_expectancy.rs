@@ -0,0 +1,246 @@
+use crate::game_state::GameState;
+use crate::markov::{plate_appearance_no_bat_probabilities, NoBatProbabilities};
+use crate::zone_model::ZoneModel;
+use crate::{PlateAppearance, PlateOutcome};
+
+// Index of a base state, first base is bit 0, second bit 1 and third bit 2
+fn bases_index(bases: &[bool; 3]) -> usize {
+    bases
+        .iter()
+        .enumerate()
+        .filter(|(_, occupied)| **occupied)
+        .map(|(base, _)| 1 << base)
+        .sum()
+}
+
+// Average runs scored from each of the 24 base-out states to the end of the half inning
+#[derive(Clone, Debug, PartialEq)]
+pub struct RunExpectancy {
+    // Indexed [outs][bases]
+    runs: [[f64; 8]; 3],
+    // How many plate appearances started in each state
+    samples: [[u32; 8]; 3],
+}
+
+impl RunExpectancy {
+    // Average the runs scored after every plate appearance whose half inning was played to three
+    // outs, grouped by the base-out state it started in
+    pub fn from_plate_appearances<'a>(
+        plate_appearances: impl IntoIterator<Item = &'a PlateAppearance>,
+    ) -> Self {
+        let mut total_runs = [[0.0; 8]; 3];
+        let mut samples = [[0; 8]; 3];
+        for appearance in plate_appearances {
+            let runs = match appearance.get_runs_to_end_of_inning() {
+                Some(runs) => runs,
+                None => continue,
+            };
+            let state = appearance.get_game_state();
+            let outs = *state.get_outs() as usize;
+            if outs > 2 {
+                continue;
+            }
+            let bases = bases_index(state.get_bases());
+            total_runs[outs][bases] += *runs as f64;
+            samples[outs][bases] += 1;
+        }
+        let mut runs = [[0.0; 8]; 3];
+        for outs in 0..3 {
+            for bases in 0..8 {
+                if samples[outs][bases] > 0 {
+                    runs[outs][bases] = total_runs[outs][bases] / samples[outs][bases] as f64;
+                }
+            }
+        }
+        Self { runs, samples }
+    }
+
+    // Expected runs to the end of the half inning, zero once it has three outs
+    pub fn get(&self, outs: i32, bases: &[bool; 3]) -> f64 {
+        if !(0..3).contains(&outs) {
+            return 0.0;
+        }
+        self.runs[outs as usize][bases_index(bases)]
+    }
+    pub fn get_samples(&self, outs: i32, bases: &[bool; 3]) -> u32 {
+        if !(0..3).contains(&outs) {
+            return 0;
+        }
+        self.samples[outs as usize][bases_index(bases)]
+    }
+    pub fn value(&self, state: &GameState) -> f64 {
+        self.get(*state.get_outs(), state.get_bases())
+    }
+
+    // RE24 of a plate appearance: the change in run expectancy plus the runs that scored
+    pub fn run_value(&self, appearance: &PlateAppearance) -> f64 {
+        self.value(appearance.get_game_state_after()) - self.value(appearance.get_game_state())
+            + appearance.runs_on_play() as f64
+    }
+
+    // RE24 of a plate appearance replayed to `outcome` from the same starting state, with runners
+    // moving only when forced
+    pub fn run_value_of_outcome(&self, state: &GameState, outcome: &PlateOutcome) -> f64 {
+        let mut after = state.clone();
+        let runs = after.apply_event(outcome.to_code());
+        self.value(&after) - self.value(state) + runs as f64
+    }
+
+    // Expected RE24 of a plate appearance without a bat. Intentional walks keep the real play.
+    pub fn expected_run_value_no_bat(
+        &self,
+        appearance: &PlateAppearance,
+        probabilities: &NoBatProbabilities,
+    ) -> f64 {
+        let state = appearance.get_game_state();
+        let mut run_value = probabilities.get_walk()
+            * self.run_value_of_outcome(state, &PlateOutcome::Walk)
+            + probabilities.get_hit_by_pitch()
+                * self.run_value_of_outcome(state, &PlateOutcome::HitByPitch)
+            + probabilities.get_strikeout()
+                * self.run_value_of_outcome(state, &PlateOutcome::Strikeout);
+        if *probabilities.get_intentional_walk() > 0.0 {
+            run_value += probabilities.get_intentional_walk() * self.run_value(appearance);
+        }
+        run_value
+    }
+
+    // Total RE24 of a set of plate appearances
+    pub fn total_run_value(&self, plate_appearances: &[PlateAppearance]) -> f64 {
+        plate_appearances
+            .iter()
+            .map(|appearance| self.run_value(appearance))
+            .sum()
+    }
+
+    // Exact expected total RE24 of a season without a bat
+    pub fn expected_total_run_value_no_bat(
+        &self,
+        plate_appearances: &[PlateAppearance],
+        oswing_pct: f32,
+        swing_pct: f32,
+        zone_model: &ZoneModel,
+    ) -> f64 {
+        plate_appearances
+            .iter()
+            .enumerate()
+            .map(|(index, appearance)| {
+                let probabilities = plate_appearance_no_bat_probabilities(
+                    appearance,
+                    oswing_pct,
+                    swing_pct,
+                    &zone_model.for_plate_appearance(index, appearance),
+                );
+                self.expected_run_value_no_bat(appearance, &probabilities)
+            })
+            .sum()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse_plate_appearances;
+
+    // MIL score two in the top of the first on a home run that follows a steal, and SFN go down
+    // in order after a walk
+    const GAME: &str = "id,SFN200404120
+info,visteam,MIL
+info,hometeam,SFN
+info,date,2004/04/12
+play,1,0,podss001,01,CX,S8/G
+play,1,0,counc001,00,,SB2
+play,1,0,counc001,11,B.CX,HR/F.2-H
+play,1,0,spivj001,02,CSS,K
+play,1,0,jenkg001,00,X,8/F
+play,1,0,durhr001,00,X,63/G
+play,1,1,grism001,30,BBBB,W
+play,1,1,snowj001,02,CSS,K
+play,1,1,bondb001,02,CSS,K
+play,1,1,alfoe001,02,CSS,K
+";
+
+    fn state(outs: i32, bases: &str) -> GameState {
+        let mut state = GameState::new();
+        for _ in 0..outs {
+            state.apply_event("K");
+        }
+        for base in bases.chars().filter_map(|base| base.to_digit(10)) {
+            state.place_runner(base as usize);
+        }
+        state
+    }
+
+    // Made-up matrix where every base-out state is worth something different
+    fn matrix() -> RunExpectancy {
+        let mut runs = [[0.0; 8]; 3];
+        for (outs, row) in runs.iter_mut().enumerate() {
+            for (bases, value) in row.iter_mut().enumerate() {
+                *value = (3 - outs) as f64 * 0.3 + bases as f64 * 0.1;
+            }
+        }
+        RunExpectancy {
+            runs,
+            samples: [[1; 8]; 3],
+        }
+    }
+
+    #[test]
+    fn matrix_from_a_half_inning() {
+        let (plate_appearances, warnings) = parse_plate_appearances(GAME, "2004SFN.EVN");
+        assert!(warnings.is_empty());
+        let matrix = RunExpectancy::from_plate_appearances(&plate_appearances);
+        let cases = [
+            // Podsednik and Spivey with none on and none out, then Grissom in the bottom half
+            (0, "", 3, 2.0 / 3.0),
+            // Counsell is keyed after the steal, with the runner on second
+            (0, "2", 1, 2.0),
+            (0, "1", 1, 0.0),
+            (1, "", 1, 0.0),
+            (1, "1", 1, 0.0),
+            (2, "", 1, 0.0),
+            (2, "1", 1, 0.0),
+            (0, "3", 0, 0.0),
+        ];
+        for (outs, bases, samples, runs) in cases {
+            let bases = *state(outs, bases).get_bases();
+            assert_eq!(
+                matrix.get_samples(outs, &bases),
+                samples,
+                "{} {:?}",
+                outs,
+                bases
+            );
+            assert!((matrix.get(outs, &bases) - runs).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn walk_with_runners_on_first_and_third_loads_the_bases() {
+        let start = state(1, "13");
+        let mut after = start.clone();
+        assert_eq!(after.apply_event(PlateOutcome::Walk.to_code()), 0);
+        assert_eq!(after.get_bases(), &[true, true, true]);
+        let matrix = matrix();
+        let expected = matrix.value(&state(1, "123")) - matrix.value(&start);
+        let run_value = matrix.run_value_of_outcome(&start, &PlateOutcome::Walk);
+        assert!((run_value - expected).abs() < 1e-12);
+    }
+
+    #[test]
+    fn bases_loaded_walk_forces_in_a_run() {
+        let matrix = matrix();
+        for outcome in [PlateOutcome::Walk, PlateOutcome::HitByPitch] {
+            let start = state(2, "123");
+            let mut after = start.clone();
+            assert_eq!(after.apply_event(outcome.to_code()), 1);
+            assert_eq!(after.get_bases(), &[true, true, true]);
+            // Same outs and bases, so the run is all it is worth
+            let run_value = matrix.run_value_of_outcome(&start, &outcome);
+            assert!((run_value - 1.0).abs() < 1e-12, "{}", run_value);
+        }
+        // A strikeout with the bases loaded ends the inning and throws away their value
+        let run_value = matrix.run_value_of_outcome(&state(2, "123"), &PlateOutcome::Strikeout);
+        assert!((run_value + matrix.value(&state(2, "123"))).abs() < 1e-12);
+    }
+}
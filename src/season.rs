@@ -0,0 +1,387 @@
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
+use std::sync::Arc;
+
+use crate::discipline::{
+    pitcher_plate_discipline_path, platoon_plate_discipline_path,
+    read_pitcher_plate_discipline_table, read_plate_discipline_table,
+    read_platoon_plate_discipline_table, DisciplineTable, PlateDiscipline,
+};
+use crate::error::{Error, Result};
+use crate::event_file::{is_event_file, read_plate_appearances_from_season_file};
+use crate::game_type::{GameSelection, GameType};
+use crate::platoon::{PlatoonSplits, PlatoonZoneRates};
+use crate::registry::{read_crosswalk_from_file, PlayerRegistry, CROSSWALK_FILE};
+use crate::roster::{read_season_rosters, Hand, RosterEntry};
+use crate::run_expectancy::RunExpectancy;
+use crate::season_file::SeasonFile;
+use crate::umpire::UmpireZoneRates;
+use crate::zone_model::{CountZoneRates, PitcherZoneRates, PitcherZoneSource};
+use crate::{list_season_files, PlateAppearance};
+
+// Retrosheet player IDs, e.g. `bondb001`
+pub type PlayerId = String;
+
+// A player's plate appearances for one team
+#[derive(Clone)]
+pub struct Stint {
+    team: String,
+    plate_appearances: Vec<PlateAppearance>,
+}
+
+impl Stint {
+    pub fn new(team: String, plate_appearances: Vec<PlateAppearance>) -> Self {
+        Self {
+            team,
+            plate_appearances,
+        }
+    }
+    pub fn get_team(&self) -> &String {
+        &self.team
+    }
+    pub fn get_plate_appearances(&self) -> &Vec<PlateAppearance> {
+        &self.plate_appearances
+    }
+}
+
+// Everything needed to simulate a season, read from disk once: every plate appearance indexed by
+// batter, the rosters, the player registry, the plate discipline table, and the run expectancy
+// matrix, called strike rates by count and by handedness matchup, pitchers' Zone% and umpires'
+// zones worked out from the plate appearances
+pub struct Season {
+    year: i32,
+    plate_appearances: HashMap<PlayerId, Vec<PlateAppearance>>,
+    roster: Vec<RosterEntry>,
+    registry: PlayerRegistry,
+    discipline: DisciplineTable,
+    run_expectancy: RunExpectancy,
+    count_zone_rates: CountZoneRates,
+    pitcher_zone_rates: HashMap<PitcherZoneSource, Arc<PitcherZoneRates>>,
+    umpire_zone_rates: Arc<UmpireZoneRates>,
+    platoon_zone_rates: PlatoonZoneRates,
+    // Batters' tables split by the pitcher's hand, where there are any
+    platoon_discipline: HashMap<Hand, DisciplineTable>,
+    // Event file records and files that were skipped because they could not be read
+    warnings: Vec<Error>,
+}
+
+// A player's row of a plate discipline table, matched by FanGraphs ID when the crosswalk has one
+// and by each of his names otherwise
+fn find_plate_discipline(
+    registry: &PlayerRegistry,
+    table: &DisciplineTable,
+    player_id: &str,
+) -> Result<PlateDiscipline> {
+    if let Some(fangraphs_id) = registry.get_fangraphs_id(player_id) {
+        match table.get_by_fangraphs_id(fangraphs_id) {
+            Err(Error::MissingDiscipline { .. }) => {}
+            result => return result,
+        }
+    }
+    let names = registry.get_names(player_id);
+    for name in &names {
+        match table.get(name) {
+            Err(Error::MissingDiscipline { .. }) => {}
+            result => return result,
+        }
+    }
+    Err(Error::MissingDiscipline {
+        name: names
+            .first()
+            .cloned()
+            .unwrap_or_else(|| player_id.to_owned()),
+        year: *table.get_year(),
+    })
+}
+
+impl Season {
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        year: i32,
+        plate_appearances: HashMap<PlayerId, Vec<PlateAppearance>>,
+        roster: Vec<RosterEntry>,
+        registry: PlayerRegistry,
+        discipline: DisciplineTable,
+        run_expectancy: RunExpectancy,
+        count_zone_rates: CountZoneRates,
+        pitcher_zone_rates: HashMap<PitcherZoneSource, Arc<PitcherZoneRates>>,
+        umpire_zone_rates: Arc<UmpireZoneRates>,
+        platoon_zone_rates: PlatoonZoneRates,
+        platoon_discipline: HashMap<Hand, DisciplineTable>,
+    ) -> Self {
+        Self {
+            year,
+            plate_appearances,
+            roster,
+            registry,
+            discipline,
+            run_expectancy,
+            count_zone_rates,
+            pitcher_zone_rates,
+            umpire_zone_rates,
+            platoon_zone_rates,
+            platoon_discipline,
+            warnings: Vec::new(),
+        }
+    }
+    pub fn with_warnings(mut self, warnings: Vec<Error>) -> Self {
+        self.warnings = warnings;
+        self
+    }
+
+    // Parse every event file, roster and the plate discipline table of `<data_dir>/<year>eve/`,
+    // with event files and rosters also read from `<data_dir>/<year>eve.zip`,
+    // plus the pitcher and platoon plate discipline tables and the `<data_dir>/people.csv`
+    // crosswalk if there are any. Only regular season games are kept.
+    pub fn load(data_dir: &Path, year: i32) -> Result<Season> {
+        Season::load_games(data_dir, year, GameSelection::Regular)
+    }
+
+    // The same, keeping the plate appearances of the games `games` picks. The league's run
+    // environment and zones still come from the regular season when the files have it.
+    pub fn load_games(data_dir: &Path, year: i32, games: GameSelection) -> Result<Season> {
+        // Files are listed in sorted order so each batter's plate appearances come out the same
+        // way every run, with the regular season ahead of the postseason and All-Star games
+        let mut files = list_season_files(data_dir, year)?
+            .into_iter()
+            .filter(is_event_file)
+            .collect::<Vec<SeasonFile>>();
+        files.sort_by_key(|file| file.extension() == Some("EVE"));
+        // A record or file that cannot be read is left out with a warning rather than costing the
+        // whole season
+        let mut all_appearances: Vec<PlateAppearance> = Vec::new();
+        let mut warnings: Vec<Error> = Vec::new();
+        for file in &files {
+            match read_plate_appearances_from_season_file(file) {
+                Ok((appearances, file_warnings)) => {
+                    all_appearances.extend(appearances);
+                    warnings.extend(file_warnings);
+                }
+                Err(e) => warnings.push(e),
+            }
+        }
+        let roster = read_season_rosters(data_dir, year)?;
+        let mut registry = PlayerRegistry::new(&roster);
+        let crosswalk_path = data_dir.join(CROSSWALK_FILE);
+        if crosswalk_path.is_file() {
+            registry.add_crosswalk(&read_crosswalk_from_file(&crosswalk_path)?);
+        }
+        // Batters and pitchers the event file did not give a hand for use their roster's
+        for appearance in &mut all_appearances {
+            let pitcher_hand = appearance.get_pitcher_hand().or_else(|| {
+                let pitcher = appearance.get_pitcher().as_ref()?;
+                registry
+                    .get_roster_entries(pitcher)
+                    .first()?
+                    .throwing_hand()
+            });
+            let batter_hand = registry
+                .get_roster_entries(appearance.get_batter())
+                .first()
+                .and_then(|entry| entry.batting_hand(pitcher_hand));
+            appearance.set_missing_hands(batter_hand, pitcher_hand);
+        }
+        let discipline = read_plate_discipline_table(data_dir, year)?;
+        let mut platoon_discipline = HashMap::new();
+        for hand in [Hand::Left, Hand::Right] {
+            if platoon_plate_discipline_path(data_dir, year, hand).is_file() {
+                platoon_discipline.insert(
+                    hand,
+                    read_platoon_plate_discipline_table(data_dir, year, hand)?,
+                );
+            }
+        }
+        // The season's own plate appearances give its run environment and strike zone
+        let regular_season = all_appearances
+            .iter()
+            .any(|appearance| appearance.get_game_type() == &GameType::Regular);
+        let league = || {
+            all_appearances.iter().filter(move |appearance| {
+                !regular_season || appearance.get_game_type() == &GameType::Regular
+            })
+        };
+        let run_expectancy = RunExpectancy::from_plate_appearances(league());
+        let count_zone_rates = CountZoneRates::from_plate_appearances(league());
+        let mut pitcher_zone_rates = HashMap::new();
+        if let Some(league_zone_pct) = discipline.mean_zone_pct() {
+            let rates = PitcherZoneRates::from_taken_pitches(league(), league_zone_pct);
+            pitcher_zone_rates.insert(PitcherZoneSource::TakenPitches, Arc::new(rates));
+        }
+        if pitcher_plate_discipline_path(data_dir, year).is_file() {
+            let table = read_pitcher_plate_discipline_table(data_dir, year)?;
+            let pitchers = all_appearances
+                .iter()
+                .filter_map(|appearance| appearance.get_pitcher().as_ref())
+                .collect::<HashSet<&String>>();
+            // Pitchers missing from the table are left to the batter's Zone%
+            let rates = pitchers
+                .into_iter()
+                .filter_map(|pitcher| {
+                    let row = find_plate_discipline(&registry, &table, pitcher).ok()?;
+                    Some((pitcher.clone(), *row.get_zone_pct()))
+                })
+                .collect();
+            pitcher_zone_rates.insert(
+                PitcherZoneSource::Discipline,
+                Arc::new(PitcherZoneRates::new(rates)),
+            );
+        }
+        let umpire_zone_rates = UmpireZoneRates::from_plate_appearances(league());
+        let platoon_zone_rates = PlatoonZoneRates::from_plate_appearances(league());
+        let mut plate_appearances: HashMap<PlayerId, Vec<PlateAppearance>> = HashMap::new();
+        for appearance in all_appearances
+            .into_iter()
+            .filter(|appearance| games.includes(*appearance.get_game_type()))
+        {
+            plate_appearances
+                .entry(appearance.get_batter().clone())
+                .or_default()
+                .push(appearance);
+        }
+        // Road games are in the home team's file, so each batter's plate appearances are put
+        // back in the order he made them. That order is what pitchers adapt along, however the
+        // season is split up afterwards.
+        for appearances in plate_appearances.values_mut() {
+            appearances.sort_by_key(|appearance| {
+                let date = appearance.get_date();
+                (*date.get_year(), *date.get_month(), *date.get_day())
+            });
+            for (index, appearance) in appearances.iter_mut().enumerate() {
+                appearance.set_season_index(index);
+            }
+        }
+        Ok(Season::new(
+            year,
+            plate_appearances,
+            roster,
+            registry,
+            discipline,
+            run_expectancy,
+            count_zone_rates,
+            pitcher_zone_rates,
+            Arc::new(umpire_zone_rates),
+            platoon_zone_rates,
+            platoon_discipline,
+        )
+        .with_warnings(warnings))
+    }
+
+    pub fn get_year(&self) -> &i32 {
+        &self.year
+    }
+    pub fn get_roster(&self) -> &Vec<RosterEntry> {
+        &self.roster
+    }
+    pub fn get_registry(&self) -> &PlayerRegistry {
+        &self.registry
+    }
+    pub fn get_discipline(&self) -> &DisciplineTable {
+        &self.discipline
+    }
+    pub fn get_run_expectancy(&self) -> &RunExpectancy {
+        &self.run_expectancy
+    }
+    pub fn get_count_zone_rates(&self) -> &CountZoneRates {
+        &self.count_zone_rates
+    }
+    pub fn get_pitcher_zone_rates(
+        &self,
+        source: PitcherZoneSource,
+    ) -> Result<&Arc<PitcherZoneRates>> {
+        self.pitcher_zone_rates
+            .get(&source)
+            .ok_or(Error::MissingPitcherDiscipline { year: self.year })
+    }
+    pub fn get_umpire_zone_rates(&self) -> &Arc<UmpireZoneRates> {
+        &self.umpire_zone_rates
+    }
+    pub fn get_platoon_zone_rates(&self) -> &PlatoonZoneRates {
+        &self.platoon_zone_rates
+    }
+    pub fn get_warnings(&self) -> &Vec<Error> {
+        &self.warnings
+    }
+    pub fn get_all_plate_appearances(&self) -> &HashMap<PlayerId, Vec<PlateAppearance>> {
+        &self.plate_appearances
+    }
+
+    // The batter's plate appearances for every team he played for, empty if he never came to
+    // the plate
+    pub fn get_plate_appearances(&self, player_id: &str) -> &[PlateAppearance] {
+        self.plate_appearances
+            .get(player_id)
+            .map(|appearances| appearances.as_slice())
+            .unwrap_or(&[])
+    }
+
+    // The batter's plate appearances split by team, in the order he played for them
+    pub fn get_stints(&self, player_id: &str) -> Vec<Stint> {
+        let mut stints: Vec<Stint> = Vec::new();
+        for appearance in self.get_plate_appearances(player_id) {
+            match stints
+                .iter_mut()
+                .find(|stint| &stint.team == appearance.get_team())
+            {
+                Some(stint) => stint.plate_appearances.push(appearance.clone()),
+                None => stints.push(Stint::new(
+                    appearance.get_team().clone(),
+                    vec![appearance.clone()],
+                )),
+            }
+        }
+        let first_date = |stint: &Stint| {
+            stint
+                .plate_appearances
+                .iter()
+                .map(|appearance| {
+                    let date = appearance.get_date();
+                    (*date.get_year(), *date.get_month(), *date.get_day())
+                })
+                .min()
+        };
+        stints.sort_by_key(first_date);
+        stints
+    }
+
+    // Resolve a player name or Retrosheet ID, optionally only among one team's players
+    pub fn find_player_id(&self, player_name: &str, team_name: Option<&str>) -> Result<PlayerId> {
+        self.registry
+            .find_player_id(player_name, team_name, self.year)
+    }
+
+    // The player's plate discipline row
+    pub fn get_plate_discipline(&self, player_id: &str) -> Result<PlateDiscipline> {
+        find_plate_discipline(&self.registry, &self.discipline, player_id)
+    }
+
+    // The batter's rates against each pitcher hand, from the split tables when he is in them and
+    // estimated from the league's matchups and his own swings otherwise
+    pub fn get_platoon_splits(&self, player_id: &str) -> Result<PlatoonSplits> {
+        let rates = self.get_plate_discipline(player_id)?.simulation_rates();
+        let estimate = self
+            .registry
+            .get_roster_entries(player_id)
+            .first()
+            .map(|entry| {
+                PlatoonSplits::estimate(
+                    rates,
+                    entry,
+                    &self.platoon_zone_rates,
+                    self.get_plate_appearances(player_id),
+                )
+            })
+            .unwrap_or_else(|| PlatoonSplits::new(rates, rates));
+        let against = |hand: Hand| match self.platoon_discipline.get(&hand) {
+            Some(table) => match find_plate_discipline(&self.registry, table, player_id) {
+                Ok(row) => Ok(row.simulation_rates()),
+                Err(Error::MissingDiscipline { .. }) => Ok(*estimate.get(hand)),
+                Err(e) => Err(e),
+            },
+            None => Ok(*estimate.get(hand)),
+        };
+        Ok(PlatoonSplits::new(
+            against(Hand::Left)?,
+            against(Hand::Right)?,
+        ))
+    }
+}
@@ -0,0 +1,73 @@
+use std::fmt;
+use std::fs::{self, File};
+use std::io::Read;
+use std::path::{Path, PathBuf};
+
+use crate::error::{Error, Result};
+
+// One file of a Retrosheet season, either extracted into `<data_dir>/<year>eve/` or still inside
+// the `<data_dir>/<year>eve.zip` archive Retrosheet distributes
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum SeasonFile {
+    Extracted(PathBuf),
+    Archived { archive: PathBuf, name: String },
+}
+
+impl SeasonFile {
+    // The name without any directory, like `2004SFN.EVN`
+    pub fn file_name(&self) -> &str {
+        match self {
+            SeasonFile::Extracted(path) => path
+                .file_name()
+                .and_then(|name| name.to_str())
+                .unwrap_or_default(),
+            SeasonFile::Archived { name, .. } => name.rsplit('/').next().unwrap_or(name),
+        }
+    }
+
+    pub fn extension(&self) -> Option<&str> {
+        self.file_name()
+            .rsplit_once('.')
+            .map(|(_, extension)| extension)
+    }
+
+    pub fn read_to_string(&self) -> Result<String> {
+        match self {
+            SeasonFile::Extracted(path) => fs::read_to_string(path).map_err(|e| Error::io(path, e)),
+            SeasonFile::Archived { archive, name } => {
+                let mut zip = open_archive(archive)?;
+                let mut file = zip.by_name(name).map_err(|e| Error::archive(archive, e))?;
+                let mut contents = String::new();
+                file.read_to_string(&mut contents)
+                    .map_err(|e| Error::io(archive.join(name), e))?;
+                Ok(contents)
+            }
+        }
+    }
+}
+
+impl fmt::Display for SeasonFile {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            SeasonFile::Extracted(path) => write!(f, "{}", path.display()),
+            SeasonFile::Archived { archive, name } => write!(f, "{}:{}", archive.display(), name),
+        }
+    }
+}
+
+fn open_archive(archive: &Path) -> Result<zip::ZipArchive<File>> {
+    let file = File::open(archive).map_err(|e| Error::io(archive, e))?;
+    zip::ZipArchive::new(file).map_err(|e| Error::archive(archive, e))
+}
+
+// Every file in the archive, leaving out directory entries
+pub fn list_archive_files(archive: &Path) -> Result<Vec<SeasonFile>> {
+    Ok(open_archive(archive)?
+        .file_names()
+        .filter(|name| !name.ends_with('/'))
+        .map(|name| SeasonFile::Archived {
+            archive: archive.to_owned(),
+            name: name.to_owned(),
+        })
+        .collect())
+}
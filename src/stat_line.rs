@@ -0,0 +1,248 @@
+use crate::{PlateAppearance, PlateOutcome};
+
+// Counting stats for a set of plate appearances, classified the way the official scorer does
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct BattingLine {
+    plate_appearances: u32,
+    at_bats: u32,
+    singles: u32,
+    doubles: u32,
+    triples: u32,
+    home_runs: u32,
+    walks: u32,
+    intentional_walks: u32,
+    hit_by_pitch: u32,
+    strikeouts: u32,
+    sacrifice_flies: u32,
+    sacrifice_bunts: u32,
+    interference: u32,
+    pitches: u32,
+    // Plate appearances with a recorded pitch sequence, the denominator of pitches per PA
+    plate_appearances_with_pitches: u32,
+}
+
+impl BattingLine {
+    pub fn from_plate_appearances(plate_appearances: &[PlateAppearance]) -> Self {
+        let mut line = BattingLine::default();
+        for appearance in plate_appearances {
+            line.add(appearance);
+        }
+        line
+    }
+
+    // A line from outcomes alone, without any pitch counts
+    pub fn from_outcomes<'a>(outcomes: impl IntoIterator<Item = &'a PlateOutcome>) -> Self {
+        let mut line = BattingLine::default();
+        for outcome in outcomes {
+            line.add_outcome(outcome);
+        }
+        line
+    }
+
+    pub fn add(&mut self, appearance: &PlateAppearance) {
+        self.add_outcome(appearance.get_outcome());
+        let pitches = appearance
+            .get_pitches()
+            .iter()
+            .filter(|pitch| pitch.is_pitch())
+            .count() as u32;
+        if pitches > 0 {
+            self.pitches += pitches;
+            self.plate_appearances_with_pitches += 1;
+        }
+    }
+
+    pub fn add_outcome(&mut self, outcome: &PlateOutcome) {
+        self.plate_appearances += 1;
+        match outcome {
+            PlateOutcome::Single => self.singles += 1,
+            PlateOutcome::Double => self.doubles += 1,
+            PlateOutcome::Triple => self.triples += 1,
+            PlateOutcome::HomeRun => self.home_runs += 1,
+            PlateOutcome::Strikeout => self.strikeouts += 1,
+            // Reaching on an error or a fielder's choice is still an at bat
+            PlateOutcome::FieldedOut | PlateOutcome::Error | PlateOutcome::FieldersChoice => {}
+            // Intentional walks are walks too, and are also counted on their own
+            PlateOutcome::Walk => self.walks += 1,
+            PlateOutcome::IntentionalWalk => {
+                self.walks += 1;
+                self.intentional_walks += 1;
+            }
+            PlateOutcome::HitByPitch => self.hit_by_pitch += 1,
+            PlateOutcome::SacrificeFly => self.sacrifice_flies += 1,
+            PlateOutcome::SacrificeBunt => self.sacrifice_bunts += 1,
+            PlateOutcome::Interference => self.interference += 1,
+        }
+        if outcome.is_hit()
+            || matches!(
+                outcome,
+                PlateOutcome::Strikeout
+                    | PlateOutcome::FieldedOut
+                    | PlateOutcome::Error
+                    | PlateOutcome::FieldersChoice
+            )
+        {
+            self.at_bats += 1;
+        }
+    }
+
+    pub fn get_plate_appearances(&self) -> &u32 {
+        &self.plate_appearances
+    }
+    pub fn get_at_bats(&self) -> &u32 {
+        &self.at_bats
+    }
+    pub fn get_singles(&self) -> &u32 {
+        &self.singles
+    }
+    pub fn get_doubles(&self) -> &u32 {
+        &self.doubles
+    }
+    pub fn get_triples(&self) -> &u32 {
+        &self.triples
+    }
+    pub fn get_home_runs(&self) -> &u32 {
+        &self.home_runs
+    }
+    // Includes intentional walks
+    pub fn get_walks(&self) -> &u32 {
+        &self.walks
+    }
+    pub fn get_intentional_walks(&self) -> &u32 {
+        &self.intentional_walks
+    }
+    pub fn get_hit_by_pitch(&self) -> &u32 {
+        &self.hit_by_pitch
+    }
+    pub fn get_strikeouts(&self) -> &u32 {
+        &self.strikeouts
+    }
+    pub fn get_sacrifice_flies(&self) -> &u32 {
+        &self.sacrifice_flies
+    }
+    pub fn get_sacrifice_bunts(&self) -> &u32 {
+        &self.sacrifice_bunts
+    }
+    pub fn get_interference(&self) -> &u32 {
+        &self.interference
+    }
+    pub fn get_pitches(&self) -> &u32 {
+        &self.pitches
+    }
+
+    pub fn hits(&self) -> u32 {
+        self.singles + self.doubles + self.triples + self.home_runs
+    }
+    pub fn total_bases(&self) -> u32 {
+        self.singles + 2 * self.doubles + 3 * self.triples + 4 * self.home_runs
+    }
+
+    pub fn batting_average(&self) -> f32 {
+        self.hits() as f32 / self.at_bats as f32
+    }
+    // (H + BB + HBP) / (AB + BB + HBP + SF). Sacrifice bunts and catcher's interference count
+    // for neither side.
+    pub fn on_base_percentage(&self) -> f32 {
+        let times_on_base = self.hits() + self.walks + self.hit_by_pitch;
+        let denominator = self.at_bats + self.walks + self.hit_by_pitch + self.sacrifice_flies;
+        times_on_base as f32 / denominator as f32
+    }
+    pub fn slugging_percentage(&self) -> f32 {
+        self.total_bases() as f32 / self.at_bats as f32
+    }
+    pub fn on_base_plus_slugging(&self) -> f32 {
+        self.on_base_percentage() + self.slugging_percentage()
+    }
+    // Isolated power, extra bases per at bat
+    pub fn isolated_power(&self) -> f32 {
+        self.slugging_percentage() - self.batting_average()
+    }
+    // Percent of plate appearances, e.g. 37.6
+    pub fn walk_pct(&self) -> f32 {
+        100.0 * self.walks as f32 / self.plate_appearances as f32
+    }
+    pub fn strikeout_pct(&self) -> f32 {
+        100.0 * self.strikeouts as f32 / self.plate_appearances as f32
+    }
+    // Only plate appearances with a recorded pitch sequence are counted
+    pub fn pitches_per_plate_appearance(&self) -> f32 {
+        self.pitches as f32 / self.plate_appearances_with_pitches as f32
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn line(outcomes: &[PlateOutcome]) -> BattingLine {
+        BattingLine::from_outcomes(outcomes)
+    }
+
+    #[test]
+    fn zero_denominators_give_nan() {
+        let empty = BattingLine::default();
+        for rate in [
+            empty.batting_average(),
+            empty.on_base_percentage(),
+            empty.slugging_percentage(),
+            empty.isolated_power(),
+            empty.walk_pct(),
+            empty.strikeout_pct(),
+            empty.pitches_per_plate_appearance(),
+        ] {
+            assert!(rate.is_nan());
+        }
+        // No at bats, but he still reached base every time he came up
+        let walks = line(&[PlateOutcome::Walk, PlateOutcome::HitByPitch]);
+        assert!(walks.batting_average().is_nan());
+        assert_eq!(walks.on_base_percentage(), 1.0);
+        assert_eq!(walks.walk_pct(), 50.0);
+        // A sacrifice bunt is in no denominator of OBP
+        assert!(line(&[PlateOutcome::SacrificeBunt])
+            .on_base_percentage()
+            .is_nan());
+    }
+
+    #[test]
+    fn sacrifice_flies_count_against_obp_and_bunts_do_not() {
+        use PlateOutcome::*;
+        let base = [Single, Walk, Strikeout, FieldedOut];
+        assert_eq!(line(&base).on_base_percentage(), 0.5);
+        let with_fly = line(&[Single, Walk, Strikeout, FieldedOut, SacrificeFly]);
+        assert_eq!(with_fly.on_base_percentage(), 0.4);
+        assert_eq!(*with_fly.get_at_bats(), 3);
+        let with_bunt = line(&[Single, Walk, Strikeout, FieldedOut, SacrificeBunt]);
+        assert_eq!(with_bunt.on_base_percentage(), 0.5);
+        assert_eq!(*with_bunt.get_at_bats(), 3);
+        let with_interference = line(&[Single, Walk, Strikeout, FieldedOut, Interference]);
+        assert_eq!(with_interference.on_base_percentage(), 0.5);
+    }
+
+    #[test]
+    fn rate_stats_on_a_hand_computed_line() {
+        use PlateOutcome::*;
+        // 8 AB: 1B, 2B, HR, K, K, out, ROE, FC; plus BB, IBB and HBP in 11 PA
+        let line = line(&[
+            Single,
+            Double,
+            HomeRun,
+            Strikeout,
+            Strikeout,
+            FieldedOut,
+            Error,
+            FieldersChoice,
+            Walk,
+            IntentionalWalk,
+            HitByPitch,
+        ]);
+        assert_eq!(*line.get_at_bats(), 8);
+        assert_eq!(*line.get_walks(), 2);
+        assert_eq!(*line.get_intentional_walks(), 1);
+        assert_eq!(line.batting_average(), 3.0 / 8.0);
+        assert_eq!(line.on_base_percentage(), 6.0 / 11.0);
+        assert_eq!(line.slugging_percentage(), 7.0 / 8.0);
+        assert_eq!(line.isolated_power(), 7.0 / 8.0 - 3.0 / 8.0);
+        assert_eq!(line.walk_pct(), 100.0 * 2.0 / 11.0);
+        assert_eq!(line.strikeout_pct(), 100.0 * 2.0 / 11.0);
+    }
+}
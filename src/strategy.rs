@@ -0,0 +1,353 @@
+use rand::Rng;
+
+use crate::codes::apply_pitch;
+use crate::game_state::GameState;
+use crate::zone_model::ZoneModel;
+use crate::{PitchCode, PitchData, PlateAppearance, PlateOutcome};
+
+// When the batter keeps the bat. In every other situation he takes the pitch.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SwingStrategy {
+    // Takes every pitch, the plain no-bat replay
+    Never,
+    // Swings exactly as he did, which gives back the real season
+    Always,
+    // Only protects with two strikes
+    TwoStrikes,
+    // Only at 2-0 and 3-1
+    HittersCounts,
+    // Only with more balls than strikes
+    AheadInCount,
+    // Only with a runner on second or third
+    RunnersInScoringPosition,
+}
+
+impl SwingStrategy {
+    pub fn all() -> [SwingStrategy; 6] {
+        [
+            SwingStrategy::Never,
+            SwingStrategy::Always,
+            SwingStrategy::TwoStrikes,
+            SwingStrategy::HittersCounts,
+            SwingStrategy::AheadInCount,
+            SwingStrategy::RunnersInScoringPosition,
+        ]
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            SwingStrategy::Never => "never",
+            SwingStrategy::Always => "always",
+            SwingStrategy::TwoStrikes => "two-strikes",
+            SwingStrategy::HittersCounts => "hitters-counts",
+            SwingStrategy::AheadInCount => "ahead",
+            SwingStrategy::RunnersInScoringPosition => "risp",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<SwingStrategy> {
+        SwingStrategy::all()
+            .into_iter()
+            .find(|strategy| strategy.name() == name)
+    }
+
+    // Whether he swings as he normally would at this count and base-out state
+    pub fn swings(&self, balls: i32, strikes: i32, state: &GameState) -> bool {
+        match self {
+            SwingStrategy::Never => false,
+            SwingStrategy::Always => true,
+            SwingStrategy::TwoStrikes => strikes == 2,
+            SwingStrategy::HittersCounts => matches!((balls, strikes), (2, 0) | (3, 1)),
+            SwingStrategy::AheadInCount => balls > strikes,
+            SwingStrategy::RunnersInScoringPosition => {
+                let bases = state.get_bases();
+                bases[1] || bases[2]
+            }
+        }
+    }
+}
+
+// Every pitch a batter saw, grouped by the count it came in, along with the plate appearance it
+// belongs to. Once a replay runs past the real pitches, his reaction at a count he swings at is
+// drawn from here.
+#[derive(Clone, Default)]
+pub struct SwingPool<'a> {
+    // Indexed [balls][strikes]
+    pitches: [[Vec<(PitchCode, &'a PlateAppearance)>; 3]; 4],
+}
+
+impl<'a> SwingPool<'a> {
+    pub fn from_plate_appearances(plate_appearances: &'a [PlateAppearance]) -> Self {
+        let mut pool = SwingPool::default();
+        for appearance in plate_appearances {
+            if appearance.get_outcome() == &PlateOutcome::IntentionalWalk
+                || appearance.pitch_data() != PitchData::Complete
+            {
+                continue;
+            }
+            let (mut balls, mut strikes) = (0, 0);
+            for pitch in appearance.get_pitches() {
+                // Nothing to learn from a pitch with no known result
+                if !pitch.is_pitch()
+                    || matches!(pitch, PitchCode::StrikeUnknownType | PitchCode::Unknown)
+                {
+                    continue;
+                }
+                pool.pitches[balls as usize][strikes as usize].push((*pitch, appearance));
+                if apply_pitch(pitch, &mut balls, &mut strikes) {
+                    break;
+                }
+            }
+        }
+        pool
+    }
+
+    pub fn get(&self, balls: i32, strikes: i32) -> &Vec<(PitchCode, &'a PlateAppearance)> {
+        &self.pitches[balls.clamp(0, 3) as usize][strikes.clamp(0, 2) as usize]
+    }
+}
+
+// A replayed plate appearance that ended on a ball in play, taking its outcome and event from
+// `source`, which is not always the plate appearance being replayed
+fn ball_in_play(
+    appearance: &PlateAppearance,
+    source: &PlateAppearance,
+    pitches: Vec<PitchCode>,
+) -> PlateAppearance {
+    let game_state_after = if std::ptr::eq(appearance, source) {
+        appearance.get_game_state_after().clone()
+    } else {
+        let mut game_state_after = appearance.get_game_state().clone();
+        game_state_after.apply_event(source.get_raw_outcome());
+        game_state_after
+    };
+    appearance.replayed(
+        *source.get_outcome(),
+        pitches,
+        source.get_raw_outcome().clone(),
+        game_state_after,
+    )
+}
+
+// Replay a plate appearance with the batter swinging only where `strategy` lets him. A pitch he
+// swung at in a situation he keeps the bat for is kept as it happened, a ball in play keeps its
+// real result, and any other swing is re-simulated as a taken pitch. Past the real pitches he
+// either takes or, where he keeps the bat, reacts as he did to a random pitch from `pool` at the
+// same count. Without trustworthy pitches the replay starts from the recorded count instead.
+#[allow(clippy::too_many_arguments)]
+pub fn simulate_plate_appearance_with_strategy<R: Rng + ?Sized>(
+    rng: &mut R,
+    appearance: &PlateAppearance,
+    strategy: &SwingStrategy,
+    pool: &SwingPool,
+    oswing_pct: f32,
+    swing_pct: f32,
+    zone_model: &ZoneModel,
+) -> PlateAppearance {
+    // Intentional walks are handed out no matter what the batter carries to the plate
+    if appearance.get_outcome() == &PlateOutcome::IntentionalWalk {
+        return appearance.clone();
+    }
+    let state = appearance.get_game_state();
+    let fallback_count = appearance.fallback_count();
+    // Ball four and a hit batter were never swung at, so without pitches to replay them from
+    // they stand whatever came before
+    if appearance.pitch_data() != PitchData::Complete
+        && matches!(
+            appearance.get_outcome(),
+            PlateOutcome::Walk | PlateOutcome::HitByPitch
+        )
+    {
+        return appearance.clone();
+    }
+    let real_pitches = match fallback_count {
+        Some(_) => &[][..],
+        None => &appearance.get_pitches()[..],
+    };
+    // The situation pitch by pitch, moved along by steals, pickoffs and other plays during the
+    // plate appearance, so the strategy sees the bases as they were for each pitch
+    let mut situation = appearance.get_game_state_at_start().clone();
+    let mut plays = appearance.get_sequence().plays();
+    let (mut balls, mut strikes) = fallback_count.unwrap_or((0, 0));
+    let mut hit_by_pitch = false;
+    // Whether any real swing was replaced, after which the real outcome no longer follows
+    let mut diverged = false;
+    let mut pitches: Vec<PitchCode> = Vec::new();
+    // In theses cases, we need the probability that the thrown pitch was outside the strike zone
+    // given that the player swung.
+    let prob_ball_given_swing = zone_model.ball_given_swing_pct(oswing_pct, swing_pct);
+    for pitch in real_pitches {
+        if pitch == &PitchCode::NonBatterPlay {
+            if let Some(Some(event)) = plays.next() {
+                situation.apply_event(event);
+            }
+        }
+        // Unknown pitches and anything that is not a pitch are left out. Strikes of unknown type
+        // count as taken strikes, as they do when checking the pitches against the count.
+        if !pitch.is_pitch() || pitch == &PitchCode::Unknown {
+            continue;
+        }
+        if pitch.is_swing() && strategy.swings(balls, strikes, &situation) {
+            pitches.push(*pitch);
+            if pitch.is_in_play() {
+                return ball_in_play(appearance, appearance, pitches);
+            }
+        } else if pitch.is_pitchout() && pitch.is_swing() {
+            // Pitchouts are balls if he lets them go
+            diverged = true;
+            pitches.push(PitchCode::Pitchout);
+        } else if pitch.is_swing() {
+            // Batter swung at the pitch, so we need to re-simulate the pitch without the bat
+            diverged = true;
+            let zone = rng.gen_range(0.0..100.0);
+            if zone <= prob_ball_given_swing {
+                pitches.push(PitchCode::Ball);
+            } else {
+                pitches.push(PitchCode::CalledStrike);
+            }
+        } else {
+            // Taken pitches keep their call and hit by pitches stay hit by pitches
+            pitches.push(*pitch);
+        }
+        let pitch = pitches[pitches.len() - 1];
+        hit_by_pitch = pitch == PitchCode::HitBatter;
+        if apply_pitch(&pitch, &mut balls, &mut strikes) {
+            break;
+        }
+    }
+    // Plays past the last real pitch replayed, all of them when the replay starts from the
+    // recorded count, still came before the final pitch
+    for event in plays.flatten() {
+        situation.apply_event(event);
+    }
+
+    let outcome = if hit_by_pitch {
+        PlateOutcome::HitByPitch
+    } else if balls == 4 {
+        PlateOutcome::Walk
+    } else if strikes == 3 {
+        PlateOutcome::Strikeout
+    } else if !diverged && strategy.swings(balls, strikes, &situation) {
+        // He did what he really did all the way through, so it ended how it really ended
+        return appearance.clone();
+    } else {
+        // Nothing left of the real plate appearance, carry on pitch by pitch
+        loop {
+            let drawn = match pool.get(balls, strikes) {
+                pitches if strategy.swings(balls, strikes, &situation) && !pitches.is_empty() => {
+                    Some(pitches[rng.gen_range(0..pitches.len())])
+                }
+                _ => None,
+            };
+            let pitch = match drawn {
+                Some((pitch, source)) if pitch.is_in_play() => {
+                    pitches.push(pitch);
+                    return ball_in_play(appearance, source, pitches);
+                }
+                Some((pitch, _)) => pitch,
+                None => {
+                    let zone = rng.gen_range(0.0..100.0);
+                    if zone <= zone_model.strike_pct(balls, strikes) {
+                        PitchCode::CalledStrike
+                    } else {
+                        PitchCode::Ball
+                    }
+                }
+            };
+            pitches.push(pitch);
+            if pitch == PitchCode::HitBatter {
+                break PlateOutcome::HitByPitch;
+            }
+            if apply_pitch(&pitch, &mut balls, &mut strikes) {
+                break if balls == 4 {
+                    PlateOutcome::Walk
+                } else {
+                    PlateOutcome::Strikeout
+                };
+            }
+        }
+    };
+    // Walks and hit batters force runners along, strikeouts add an out
+    let mut game_state_after = state.clone();
+    game_state_after.apply_event(outcome.to_code());
+    appearance.replayed(
+        outcome,
+        pitches,
+        appearance.get_raw_outcome().clone(),
+        game_state_after,
+    )
+}
+
+// Replay every plate appearance of a season once under a strategy. The pool is built from the
+// same plate appearances.
+pub fn simulate_season_with_strategy<R: Rng + ?Sized>(
+    rng: &mut R,
+    plate_appearances: &[PlateAppearance],
+    strategy: &SwingStrategy,
+    pool: &SwingPool,
+    oswing_pct: f32,
+    swing_pct: f32,
+    zone_model: &ZoneModel,
+) -> Vec<PlateAppearance> {
+    plate_appearances
+        .iter()
+        .enumerate()
+        .map(|(index, appearance)| {
+            simulate_plate_appearance_with_strategy(
+                rng,
+                appearance,
+                strategy,
+                pool,
+                oswing_pct,
+                swing_pct,
+                &zone_model.for_plate_appearance(index, appearance),
+            )
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::PitchSequence;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
+    // Runner on first when he comes up, the pitches recorded against a count they do not add up
+    // to, and `play` between the first two pitches
+    fn with_runner_on_first(play: &str) -> PlateAppearance {
+        let mut start = GameState::new();
+        start.place_runner(1);
+        let mut before_final_play = start.clone();
+        before_final_play.apply_event(play);
+        let mut appearance = PlateAppearance::fixture(PlateOutcome::FieldedOut, "B.CX");
+        appearance.game_state = before_final_play;
+        appearance
+            .with_game_state_at_start(start)
+            .with_sequence(PitchSequence::parse("B.CX").with_plays([play.to_owned()]))
+            .with_count(0, 0)
+    }
+
+    #[test]
+    fn scoring_position_follows_plays_before_the_recorded_count() {
+        let replay = |appearance: &PlateAppearance| {
+            let replayed = simulate_plate_appearance_with_strategy(
+                &mut ChaCha8Rng::seed_from_u64(7),
+                appearance,
+                &SwingStrategy::RunnersInScoringPosition,
+                &SwingPool::default(),
+                25.0,
+                45.0,
+                &ZoneModel::uniform(0.0),
+            );
+            *replayed.get_outcome()
+        };
+        // Stole second, so he keeps the bat at the recorded count and the real final play stands
+        assert_eq!(
+            replay(&with_runner_on_first("SB2")),
+            PlateOutcome::FieldedOut
+        );
+        // Thrown out stealing, so he takes four balls
+        assert_eq!(replay(&with_runner_on_first("CS2(26)")), PlateOutcome::Walk);
+    }
+}
@@ -0,0 +1,154 @@
+use std::collections::HashMap;
+
+use crate::zone_model::taken_pitch_counts;
+use crate::PlateAppearance;
+
+// Taken pitches an umpire's called strike rate is shrunk toward the league's by
+const UMPIRE_PRIOR_PITCHES: f32 = 300.0;
+
+// Umpires need this many taken pitches to count toward the generous and tight ends of the league
+const QUALIFYING_PITCHES: u32 = 1000;
+
+// How each home plate umpire called the pitches batters took, keyed by Retrosheet ID
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct UmpireZoneRates {
+    // Called strikes and taken pitches
+    taken: HashMap<String, (u32, u32)>,
+    // League called strike rate on taken pitches, percent
+    league_pct: f32,
+}
+
+impl UmpireZoneRates {
+    pub fn from_plate_appearances<'a>(
+        plate_appearances: impl IntoIterator<Item = &'a PlateAppearance>,
+    ) -> Self {
+        let mut taken: HashMap<String, (u32, u32)> = HashMap::new();
+        for appearance in plate_appearances {
+            let umpire = match appearance.get_umpire() {
+                Some(umpire) => umpire,
+                None => continue,
+            };
+            let (called_strikes, samples) = taken_pitch_counts(appearance);
+            let taken = taken.entry(umpire.clone()).or_default();
+            taken.0 += called_strikes;
+            taken.1 += samples;
+        }
+        let (called_strikes, samples) = taken
+            .values()
+            .fold((0, 0), |(strikes, total), (s, n)| (strikes + s, total + n));
+        let league_pct = if samples > 0 {
+            100.0 * called_strikes as f32 / samples as f32
+        } else {
+            0.0
+        };
+        Self { taken, league_pct }
+    }
+
+    pub fn get_league_pct(&self) -> &f32 {
+        &self.league_pct
+    }
+    pub fn get_samples(&self, umpire: &str) -> u32 {
+        self.taken.get(umpire).map_or(0, |(_, samples)| *samples)
+    }
+
+    // The umpire's called strike rate on taken pitches, shrunk toward the league's
+    pub fn strike_pct(&self, umpire: &str) -> f32 {
+        let (called_strikes, samples) = self.taken.get(umpire).copied().unwrap_or_default();
+        (100.0 * called_strikes as f32 + UMPIRE_PRIOR_PITCHES * self.league_pct)
+            / (samples as f32 + UMPIRE_PRIOR_PITCHES)
+    }
+
+    // How many more strikes than average he calls, e.g. 1.05 for 5% more. Every pitch's chance of
+    // being a strike is scaled by this.
+    pub fn factor(&self, umpire: &str) -> f32 {
+        if self.league_pct > 0.0 {
+            self.strike_pct(umpire) / self.league_pct
+        } else {
+            1.0
+        }
+    }
+
+    // Factor of the umpire at `percent` (0 to 100) of the qualified umpires, from the tightest
+    // zone to the most generous one
+    pub fn percentile_factor(&self, percent: f32) -> f32 {
+        let mut factors = self
+            .taken
+            .iter()
+            .filter(|(_, (_, samples))| *samples >= QUALIFYING_PITCHES)
+            .map(|(umpire, _)| self.factor(umpire))
+            .collect::<Vec<f32>>();
+        if factors.is_empty() {
+            return 1.0;
+        }
+        factors.sort_by(|a, b| a.total_cmp(b));
+        let rank = (percent.clamp(0.0, 100.0) / 100.0 * (factors.len() - 1) as f32).round();
+        factors[rank as usize]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::PlateOutcome;
+
+    fn rates(taken: &[(&str, u32, u32)], league_pct: f32) -> UmpireZoneRates {
+        UmpireZoneRates {
+            taken: taken
+                .iter()
+                .map(|(umpire, called_strikes, samples)| {
+                    (umpire.to_string(), (*called_strikes, *samples))
+                })
+                .collect(),
+            league_pct,
+        }
+    }
+
+    #[test]
+    fn strike_pct_is_shrunk_toward_the_league() {
+        let appearance = |umpire: &str, pitches: &str| {
+            PlateAppearance::fixture(PlateOutcome::Walk, pitches).with_umpire(umpire.to_owned())
+        };
+        let mut plate_appearances = Vec::new();
+        for _ in 0..100 {
+            plate_appearances.push(appearance("westj901", "CBBBB"));
+            // Four balls recorded as an 0-2 count say nothing about his zone
+            plate_appearances.push(appearance("westj901", "CCCC").with_count(0, 2));
+        }
+        for _ in 0..125 {
+            plate_appearances.push(appearance("kellj901", "BBBB"));
+        }
+        let umpires = UmpireZoneRates::from_plate_appearances(&plate_appearances);
+        assert_eq!(umpires.get_samples("westj901"), 500);
+        assert_eq!(*umpires.get_league_pct(), 10.0);
+        // 100 called strikes in 500 pitches plus 300 at the league's 10%
+        assert!((umpires.strike_pct("westj901") - 13000.0 / 800.0).abs() < 1e-4);
+        assert!((umpires.strike_pct("kellj901") - 3000.0 / 800.0).abs() < 1e-4);
+        // Nothing to go on but the league
+        assert_eq!(umpires.strike_pct("nobody"), 10.0);
+        assert_eq!(umpires.factor("nobody"), 1.0);
+    }
+
+    #[test]
+    fn percentile_factor_only_counts_qualified_umpires() {
+        // Nobody has called 1000 pitches, so every umpire is average
+        let umpires = rates(&[("westj901", 600, 999), ("kellj901", 0, 999)], 25.0);
+        for percent in [0.0, 50.0, 100.0] {
+            assert_eq!(umpires.percentile_factor(percent), 1.0);
+        }
+        // The umpire a pitch short of qualifying would be the most generous
+        let umpires = rates(
+            &[
+                ("westj901", 200, 1000),
+                ("kellj901", 250, 1000),
+                ("randt901", 300, 1000),
+                ("hallt901", 900, 999),
+            ],
+            25.0,
+        );
+        let factor = |called_strikes: f32| (100.0 * called_strikes + 300.0 * 25.0) / 1300.0 / 25.0;
+        for (percent, expected) in [(0.0, factor(200.0)), (50.0, 1.0), (100.0, factor(300.0))] {
+            let actual = umpires.percentile_factor(percent);
+            assert!((actual - expected).abs() < 1e-5, "{}: {}", percent, actual);
+        }
+    }
+}
@@ -0,0 +1,606 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use crate::platoon::PlatoonSplits;
+use crate::umpire::UmpireZoneRates;
+use crate::{PitchCode, PitchData, PlateAppearance};
+
+// League-wide share of taken pitches called strikes at each ball-strike count, read off the
+// season's pitch sequences, along with the pitches swung at. Pitchers throw far more strikes at
+// 3-0 than at 0-2.
+#[derive(Clone, Debug, PartialEq)]
+pub struct CountZoneRates {
+    // Indexed [balls][strikes], percent
+    strike_pct: [[f32; 3]; 4],
+    // Taken pitches seen at each count
+    samples: [[u32; 3]; 4],
+    // Called strikes among them
+    called_strikes: [[u32; 3]; 4],
+    // Pitches swung at, leaving out pitchouts
+    swings: [[u32; 3]; 4],
+    // Called strike rate over every count
+    overall_pct: f32,
+}
+
+impl CountZoneRates {
+    // Count every taken ball, called strike and swing by the count it was thrown in. Intentional
+    // balls, pitchouts and automatic calls say nothing about where pitchers aim and are only used
+    // to keep the count.
+    pub fn from_plate_appearances<'a>(
+        plate_appearances: impl IntoIterator<Item = &'a PlateAppearance>,
+    ) -> Self {
+        let mut called_strikes = [[0; 3]; 4];
+        let mut samples = [[0; 3]; 4];
+        let mut swings = [[0; 3]; 4];
+        for appearance in plate_appearances {
+            // Counts are only known along a complete sequence
+            if appearance.pitch_data() != PitchData::Complete {
+                continue;
+            }
+            let mut balls = 0;
+            let mut strikes = 0;
+            for pitch in appearance.get_pitches() {
+                if balls > 3 || strikes > 2 {
+                    break;
+                }
+                match pitch {
+                    PitchCode::Ball => samples[balls][strikes] += 1,
+                    PitchCode::CalledStrike => {
+                        samples[balls][strikes] += 1;
+                        called_strikes[balls][strikes] += 1;
+                    }
+                    pitch if pitch.is_swing() && !pitch.is_pitchout() => {
+                        swings[balls][strikes] += 1
+                    }
+                    _ => {}
+                }
+                if pitch.is_ball() {
+                    balls += 1;
+                } else if pitch.is_foul() {
+                    strikes = std::cmp::min(strikes + 1, 2);
+                } else if pitch.is_called_strike()
+                    || (pitch.is_swing() && !pitch.is_in_play())
+                    || pitch == &PitchCode::StrikeUnknownType
+                {
+                    strikes += 1;
+                }
+            }
+        }
+        let mut strike_pct = [[0.0; 3]; 4];
+        for balls in 0..4 {
+            for strikes in 0..3 {
+                if samples[balls][strikes] > 0 {
+                    strike_pct[balls][strikes] = 100.0 * called_strikes[balls][strikes] as f32
+                        / samples[balls][strikes] as f32;
+                }
+            }
+        }
+        let total_samples = samples.iter().flatten().sum::<u32>();
+        let total_called_strikes = called_strikes.iter().flatten().sum::<u32>();
+        let overall_pct = if total_samples > 0 {
+            100.0 * total_called_strikes as f32 / total_samples as f32
+        } else {
+            0.0
+        };
+        Self {
+            strike_pct,
+            samples,
+            called_strikes,
+            swings,
+            overall_pct,
+        }
+    }
+
+    pub fn get(&self, balls: usize, strikes: usize) -> f32 {
+        self.strike_pct[balls.min(3)][strikes.min(2)]
+    }
+    pub fn get_overall_pct(&self) -> &f32 {
+        &self.overall_pct
+    }
+    pub fn get_samples(&self, balls: usize, strikes: usize) -> u32 {
+        self.samples[balls.min(3)][strikes.min(2)]
+    }
+
+    // Estimated share of all pitches in the zone at each count, None for a count nobody saw a
+    // pitch in. Batters take pitches in the zone far less often with two strikes, so called
+    // strikes alone make those counts look like pitchers never throw strikes. Here taken
+    // balls are outside the zone, called strikes inside, and swings inside at whatever share
+    // brings the league's pitches to `league_zone_pct` overall.
+    pub fn zone_shape(&self, league_zone_pct: f32) -> [[Option<f32>; 3]; 4] {
+        let total = |counts: &[[u32; 3]; 4]| counts.iter().flatten().sum::<u32>() as f32;
+        let pitches = total(&self.samples) + total(&self.swings);
+        let swings = total(&self.swings);
+        let swings_in_zone = if swings > 0.0 {
+            ((league_zone_pct / 100.0 * pitches - total(&self.called_strikes)) / swings)
+                .clamp(0.0, 1.0)
+        } else {
+            0.0
+        };
+        let mut shape = [[None; 3]; 4];
+        for (balls, row) in shape.iter_mut().enumerate() {
+            for (strikes, pct) in row.iter_mut().enumerate() {
+                let swings = self.swings[balls][strikes] as f32;
+                let pitches = self.samples[balls][strikes] as f32 + swings;
+                if pitches > 0.0 {
+                    let in_zone =
+                        self.called_strikes[balls][strikes] as f32 + swings_in_zone * swings;
+                    *pct = Some(100.0 * in_zone / pitches);
+                }
+            }
+        }
+        shape
+    }
+}
+
+// Share of pitches that are strikes over a plate appearance in which the batter takes every
+// pitch, each count weighted by how likely he is to see a pitch in it
+pub fn no_swing_zone_pct(strike_pct: &[[f32; 3]; 4]) -> f32 {
+    let mut reached = [[0.0; 3]; 4];
+    reached[0][0] = 1.0;
+    let mut pitches = 0.0;
+    let mut strikes = 0.0;
+    for balls in 0..4 {
+        for strike_count in 0..3 {
+            let chance = reached[balls][strike_count];
+            let strike = strike_pct[balls][strike_count] / 100.0;
+            pitches += chance;
+            strikes += chance * strike;
+            if strike_count < 2 {
+                reached[balls][strike_count + 1] += chance * strike;
+            }
+            if balls < 3 {
+                reached[balls + 1][strike_count] += chance * (1.0 - strike);
+            }
+        }
+    }
+    100.0 * strikes / pitches
+}
+
+// Called strikes and taken balls and strikes in a plate appearance, none when its pitches may be
+// missing or misrecorded
+pub(crate) fn taken_pitch_counts(appearance: &PlateAppearance) -> (u32, u32) {
+    if appearance.pitch_data() != PitchData::Complete {
+        return (0, 0);
+    }
+    appearance
+        .get_pitches()
+        .iter()
+        .fold((0, 0), |(called_strikes, samples), pitch| match pitch {
+            PitchCode::Ball => (called_strikes, samples + 1),
+            PitchCode::CalledStrike => (called_strikes + 1, samples + 1),
+            _ => (called_strikes, samples),
+        })
+}
+
+// Where each pitcher's Zone% comes from
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum PitcherZoneSource {
+    // The season's pitcher plate discipline table
+    Discipline,
+    // Called strikes on the pitches batters took against him
+    TakenPitches,
+}
+
+// Taken pitches a pitcher's called strike rate is shrunk toward the league's by, so one who only
+// threw a handful is treated as about average
+const PITCHER_PRIOR_PITCHES: f32 = 100.0;
+
+// Zone% of the pitchers in a season, keyed by Retrosheet ID
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct PitcherZoneRates {
+    zone_pct: HashMap<String, f32>,
+}
+
+impl PitcherZoneRates {
+    pub fn new(zone_pct: HashMap<String, f32>) -> Self {
+        Self { zone_pct }
+    }
+
+    // Taken pitches are mostly balls, so called strike rates are put on the Zone% scale by
+    // their ratio to the league's rate times the league's average Zone%
+    pub fn from_taken_pitches<'a>(
+        plate_appearances: impl IntoIterator<Item = &'a PlateAppearance>,
+        league_zone_pct: f32,
+    ) -> Self {
+        let mut taken: HashMap<&str, (u32, u32)> = HashMap::new();
+        for appearance in plate_appearances {
+            let pitcher = match appearance.get_pitcher() {
+                Some(pitcher) => pitcher,
+                None => continue,
+            };
+            let (called_strikes, samples) = taken_pitch_counts(appearance);
+            let taken = taken.entry(pitcher).or_default();
+            taken.0 += called_strikes;
+            taken.1 += samples;
+        }
+        let (league_called_strikes, league_samples) =
+            taken.values().fold((0, 0), |(strikes, samples), (s, n)| {
+                (strikes + s, samples + n)
+            });
+        if league_samples == 0 {
+            return Self::default();
+        }
+        let league_pct = league_called_strikes as f32 / league_samples as f32;
+        let zone_pct = taken
+            .into_iter()
+            .map(|(pitcher, (called_strikes, samples))| {
+                let pct = (called_strikes as f32 + PITCHER_PRIOR_PITCHES * league_pct)
+                    / (samples as f32 + PITCHER_PRIOR_PITCHES);
+                (pitcher.to_owned(), league_zone_pct * pct / league_pct)
+            })
+            .collect();
+        Self { zone_pct }
+    }
+
+    pub fn get(&self, pitcher: &str) -> Option<f32> {
+        self.zone_pct.get(pitcher).copied()
+    }
+    pub fn len(&self) -> usize {
+        self.zone_pct.len()
+    }
+    pub fn is_empty(&self) -> bool {
+        self.zone_pct.is_empty()
+    }
+}
+
+// Pitchers catching on to a batter who never swings. For the first `after` plate appearances they
+// pitch to him as usual, then over the next `over` they move every count's strike rate up to
+// `target_pct`, their Zone% when simply pounding the zone.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct PitcherAdaptation {
+    after: usize,
+    over: usize,
+    target_pct: f32,
+}
+
+impl PitcherAdaptation {
+    pub fn new(after: usize, over: usize, target_pct: f32) -> Self {
+        Self {
+            after,
+            over,
+            target_pct,
+        }
+    }
+    pub fn get_after(&self) -> &usize {
+        &self.after
+    }
+    pub fn get_over(&self) -> &usize {
+        &self.over
+    }
+    pub fn get_target_pct(&self) -> &f32 {
+        &self.target_pct
+    }
+
+    // How far along the league is, 0 to 1, by the batter's `plate_appearance`th trip to the
+    // plate (counting from 0)
+    pub fn weight(&self, plate_appearance: usize) -> f32 {
+        if plate_appearance < self.after {
+            0.0
+        } else if self.over == 0 {
+            1.0
+        } else {
+            ((plate_appearance - self.after + 1) as f32 / self.over as f32).min(1.0)
+        }
+    }
+}
+
+// Chance that a pitch the batter lets go is a strike. `zone_pct` is the batter's FanGraphs Zone%,
+// which is also what a swing is re-simulated against; `strike_pct` is what every taken pitch is
+// called from each count.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ZoneModel {
+    zone_pct: f32,
+    strike_pct: [[f32; 3]; 4],
+    adaptation: Option<PitcherAdaptation>,
+    // Each pitcher's Zone% and the weight it gets against the batter's
+    pitchers: Option<(Arc<PitcherZoneRates>, f32)>,
+    // How the home plate umpire of each game called taken pitches
+    umpires: Option<Arc<UmpireZoneRates>>,
+    // The batter's rates against each pitcher hand
+    platoon: Option<PlatoonSplits>,
+    // O-Swing% and Swing% against this plate appearance's pitcher, once resolved
+    swing_rates: Option<(f32, f32)>,
+}
+
+impl ZoneModel {
+    // Zone% at every count
+    pub fn uniform(zone_pct: f32) -> Self {
+        Self::from_rates(zone_pct, [[zone_pct; 3]; 4])
+    }
+
+    // The league's Zone% by count from `rates`, scaled so that over a plate appearance the
+    // batter never swings in they come to a Zone% between the league's average and his own,
+    // `player_weight` (0 to 1) of the way to his. Only how the rates rise and fall from count to
+    // count is kept, not their level.
+    pub fn by_count(
+        rates: &CountZoneRates,
+        league_zone_pct: f32,
+        zone_pct: f32,
+        player_weight: f32,
+    ) -> Self {
+        let player_weight = player_weight.clamp(0.0, 1.0);
+        let target_pct = (1.0 - player_weight) * league_zone_pct + player_weight * zone_pct;
+        // Counts nobody saw a pitch in follow the league's average
+        let shape = rates
+            .zone_shape(league_zone_pct)
+            .map(|row| row.map(|pct| pct.unwrap_or(league_zone_pct)));
+        if shape.iter().flatten().all(|pct| *pct <= 0.0) {
+            return Self::uniform(zone_pct).with_strike_pct(target_pct);
+        }
+        let scaled = |factor: f32| shape.map(|row| row.map(|pct| (pct * factor).min(100.0)));
+        // The no-swing Zone% grows with the factor, so it is found by bisection. At the upper
+        // end every count with any strikes at all is a certain strike.
+        let smallest = shape
+            .iter()
+            .flatten()
+            .filter(|pct| **pct > 0.0)
+            .fold(f32::INFINITY, |smallest, pct| smallest.min(*pct));
+        let (mut low, mut high) = (0.0, 100.0 / smallest);
+        for _ in 0..60 {
+            let middle = (low + high) / 2.0;
+            if no_swing_zone_pct(&scaled(middle)) < target_pct {
+                low = middle;
+            } else {
+                high = middle;
+            }
+        }
+        Self::from_rates(zone_pct, scaled((low + high) / 2.0))
+    }
+
+    // Every count at the same rate, keeping the batter's Zone% for swings
+    fn with_strike_pct(mut self, strike_pct: f32) -> Self {
+        self.strike_pct = [[strike_pct.clamp(0.0, 100.0); 3]; 4];
+        self
+    }
+
+    fn from_rates(zone_pct: f32, strike_pct: [[f32; 3]; 4]) -> Self {
+        Self {
+            zone_pct,
+            strike_pct,
+            adaptation: None,
+            pitchers: None,
+            umpires: None,
+            platoon: None,
+            swing_rates: None,
+        }
+    }
+
+    // Every count's rate and Zone% multiplied by `factor`
+    fn scale(&mut self, factor: f32) {
+        self.zone_pct = (self.zone_pct * factor).min(100.0);
+        for pct in self.strike_pct.iter_mut().flatten() {
+            *pct = (*pct * factor).min(100.0);
+        }
+    }
+
+    // Mix `pitcher_weight` (0 to 1) of each pitcher's Zone% into the batter's. Pitchers
+    // without a rate leave the batter's alone.
+    pub fn with_pitchers(mut self, pitchers: Arc<PitcherZoneRates>, pitcher_weight: f32) -> Self {
+        self.pitchers = Some((pitchers, pitcher_weight.clamp(0.0, 1.0)));
+        self
+    }
+
+    // Scale each plate appearance's rates by how its game's home plate umpire called strikes
+    pub fn with_umpires(mut self, umpires: Arc<UmpireZoneRates>) -> Self {
+        self.umpires = Some(umpires);
+        self
+    }
+
+    // Every rate scaled by `factor`, e.g. for an umpire with a generous or a tight zone
+    pub fn scaled(&self, factor: f32) -> ZoneModel {
+        let mut zone_model = self.clone();
+        zone_model.scale(factor);
+        zone_model
+    }
+
+    // Use the batter's rates against the pitcher's hand whenever it is known
+    pub fn with_platoon(mut self, platoon: PlatoonSplits) -> Self {
+        self.platoon = Some(platoon);
+        self
+    }
+
+    pub fn with_adaptation(mut self, adaptation: PitcherAdaptation) -> Self {
+        self.adaptation = Some(adaptation);
+        self
+    }
+    pub fn get_adaptation(&self) -> &Option<PitcherAdaptation> {
+        &self.adaptation
+    }
+
+    // The model for one of the batter's plate appearances. Pitchers adapt by where it falls in
+    // his season, or by `index`, its place among the plate appearances being replayed (counting
+    // from 0), when it does not come from a loaded season.
+    // His rates against the pitcher's hand, the pitcher's Zone% and then the umpire scale every
+    // count's rate, then pitchers who have adapted raise any rate still below their target; ones
+    // already above it, like at 3-0, are left alone.
+    pub fn for_plate_appearance(&self, index: usize, appearance: &PlateAppearance) -> ZoneModel {
+        let mut zone_model = Self::from_rates(self.zone_pct, self.strike_pct);
+        let platoon_rates = self
+            .platoon
+            .as_ref()
+            .zip(*appearance.get_pitcher_hand())
+            .map(|(platoon, hand)| *platoon.get(hand));
+        if let Some((oswing_pct, swing_pct, zone_pct)) = platoon_rates {
+            if self.zone_pct > 0.0 {
+                zone_model.scale(zone_pct / self.zone_pct);
+            }
+            zone_model.swing_rates = Some((oswing_pct, swing_pct));
+        }
+        let batter_zone_pct = zone_model.zone_pct;
+        let pitcher_zone_pct = self.pitchers.as_ref().and_then(|(pitchers, weight)| {
+            let pitcher = appearance.get_pitcher().as_ref()?;
+            Some((1.0 - weight) * batter_zone_pct + weight * pitchers.get(pitcher)?)
+        });
+        if let Some(pitcher_zone_pct) = pitcher_zone_pct.filter(|_| batter_zone_pct > 0.0) {
+            zone_model.scale(pitcher_zone_pct / batter_zone_pct);
+        }
+        if let Some((umpires, umpire)) = self.umpires.as_ref().zip(appearance.get_umpire().as_ref())
+        {
+            zone_model.scale(umpires.factor(umpire));
+        }
+        if let Some(adaptation) = &self.adaptation {
+            let weight = adaptation.weight(appearance.get_season_index().unwrap_or(index));
+            let adjust = |pct: f32| pct + weight * (adaptation.target_pct - pct).max(0.0);
+            for pct in zone_model.strike_pct.iter_mut().flatten() {
+                *pct = adjust(*pct);
+            }
+            zone_model.zone_pct = adjust(zone_model.zone_pct);
+        }
+        zone_model
+    }
+
+    // Percent chance that a pitch the batter swung at was a ball, by Bayes' rule:
+    // p(outside zone | swing) = p(swing | outside zone) * p(outside zone) / p(swing) - or -
+    // p(outside zone | swing) = (OSwing% * (1 - Zone%)) / Swing%
+    // The batter's own rates against the pitcher's hand replace `oswing_pct` and `swing_pct`
+    // when there are any.
+    pub fn ball_given_swing_pct(&self, oswing_pct: f32, swing_pct: f32) -> f32 {
+        let (oswing_pct, swing_pct) = self.swing_rates.unwrap_or((oswing_pct, swing_pct));
+        oswing_pct * (100.0 - self.zone_pct) / swing_pct
+    }
+
+    pub fn get_zone_pct(&self) -> &f32 {
+        &self.zone_pct
+    }
+
+    // Percent chance a taken pitch is a strike at this count
+    pub fn strike_pct(&self, balls: i32, strikes: i32) -> f32 {
+        self.strike_pct[balls.clamp(0, 3) as usize][strikes.clamp(0, 2) as usize]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::PlateOutcome;
+
+    // Made-up counts shaped like a real season's: taken pitches are mostly balls, more so with
+    // two strikes, when batters swing at most strikes
+    fn league_rates() -> CountZoneRates {
+        let called_strikes = [
+            [420, 200, 80],
+            [400, 230, 100],
+            [460, 270, 130],
+            [620, 350, 170],
+        ];
+        let samples = [[1000; 3]; 4];
+        let swings = [
+            [400, 850, 1000],
+            [700, 1100, 1400],
+            [650, 1500, 1900],
+            [90, 1200, 2800],
+        ];
+        let mut strike_pct = [[0.0; 3]; 4];
+        for balls in 0..4 {
+            for strikes in 0..3 {
+                strike_pct[balls][strikes] = called_strikes[balls][strikes] as f32 / 10.0;
+            }
+        }
+        CountZoneRates {
+            strike_pct,
+            samples,
+            called_strikes,
+            swings,
+            overall_pct: 28.0,
+        }
+    }
+
+    fn strike_pcts(zone_model: &ZoneModel) -> [[f32; 3]; 4] {
+        let mut strike_pct = [[0.0; 3]; 4];
+        for (balls, row) in strike_pct.iter_mut().enumerate() {
+            for (strikes, pct) in row.iter_mut().enumerate() {
+                *pct = zone_model.strike_pct(balls as i32, strikes as i32);
+            }
+        }
+        strike_pct
+    }
+
+    #[test]
+    fn uniform_rates_average_to_zone_pct() {
+        assert!((no_swing_zone_pct(&[[48.0; 3]; 4]) - 48.0).abs() < 1e-4);
+    }
+
+    #[test]
+    fn by_count_averages_to_zone_pct_without_swings() {
+        let rates = league_rates();
+        for (league_zone_pct, zone_pct, weight, expected) in [
+            (50.0, 34.3, 1.0, 34.3),
+            (50.0, 58.0, 1.0, 58.0),
+            (50.0, 34.3, 0.0, 50.0),
+            (50.0, 40.0, 0.5, 45.0),
+        ] {
+            let zone_model = ZoneModel::by_count(&rates, league_zone_pct, zone_pct, weight);
+            let average = no_swing_zone_pct(&strike_pcts(&zone_model));
+            assert!(
+                (average - expected).abs() < 0.01,
+                "Zone% {} weight {}: averages {}",
+                zone_pct,
+                weight,
+                average
+            );
+            assert_eq!(*zone_model.get_zone_pct(), zone_pct);
+        }
+    }
+
+    #[test]
+    fn zone_shape_counts_swings_as_pitches_in_the_zone() {
+        let rates = league_rates();
+        let shape = rates.zone_shape(50.0);
+        // Taken pitches alone would put 0-2 at 8%
+        let zero_two = shape[0][2].unwrap();
+        assert!(zero_two > 30.0, "0-2 at {}", zero_two);
+        assert!(shape[3][0].unwrap() > shape[0][0].unwrap());
+        // Every pitch together is at the league's Zone%
+        let pitches = (0..4)
+            .flat_map(|balls| (0..3).map(move |strikes| (balls, strikes)))
+            .map(|(balls, strikes)| {
+                let pitches = (rates.samples[balls][strikes] + rates.swings[balls][strikes]) as f32;
+                (pitches, pitches * shape[balls][strikes].unwrap())
+            })
+            .fold((0.0, 0.0), |(total, zone), (n, z)| (total + n, zone + z));
+        assert!((pitches.1 / pitches.0 - 50.0).abs() < 0.01);
+    }
+
+    #[test]
+    fn by_count_keeps_the_league_shape() {
+        let rates = league_rates();
+        let shape = rates.zone_shape(50.0);
+        let zone_model = ZoneModel::by_count(&rates, 50.0, 50.0, 1.0);
+        let ratio = zone_model.strike_pct(1, 1) / zone_model.strike_pct(0, 0);
+        assert!((ratio - shape[1][1].unwrap() / shape[0][0].unwrap()).abs() < 1e-4);
+    }
+
+    #[test]
+    fn by_count_without_samples_is_uniform() {
+        let rates = CountZoneRates::from_plate_appearances(&[]);
+        let zone_model = ZoneModel::by_count(&rates, 50.0, 40.0, 1.0);
+        for pct in strike_pcts(&zone_model).iter().flatten() {
+            assert!((pct - 40.0).abs() < 0.01, "{}", pct);
+        }
+    }
+
+    #[test]
+    fn pitcher_rates_skip_pitches_that_do_not_add_up() {
+        let appearance = |pitcher: &str, pitches: &str| {
+            PlateAppearance::fixture(PlateOutcome::Walk, pitches).with_pitcher(pitcher.to_owned())
+        };
+        let plate_appearances = [
+            appearance("schmj001", "BCBCBB"),
+            appearance("schmj001", "CBBBB"),
+            appearance("sheeb001", "BBBB"),
+            // Four balls recorded as an 0-2 count, and pitches nobody recorded
+            appearance("sheeb001", "CCCC").with_count(0, 2),
+            appearance("sheeb001", "??"),
+        ];
+        let rates = PitcherZoneRates::from_taken_pitches(&plate_appearances, 50.0);
+        // Three called strikes in 15 taken pitches, all of them Schmidt's
+        let league_pct = 3.0 / 15.0;
+        let expected = |called_strikes: f32, samples: f32| {
+            let pct = (called_strikes + PITCHER_PRIOR_PITCHES * league_pct)
+                / (samples + PITCHER_PRIOR_PITCHES);
+            50.0 * pct / league_pct
+        };
+        assert!((rates.get("schmj001").unwrap() - expected(3.0, 11.0)).abs() < 1e-4);
+        assert!((rates.get("sheeb001").unwrap() - expected(0.0, 4.0)).abs() < 1e-4);
+    }
+}
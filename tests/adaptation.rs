@@ -0,0 +1,35 @@
+use std::path::Path;
+
+use no_bat_simulator::{PitcherAdaptation, Season, ZoneModel};
+
+#[test]
+fn pitchers_adapt_along_the_whole_season() {
+    let season = Season::load(Path::new("tests/fixtures"), 2004).unwrap();
+    let player_id = season.find_player_id("Barry Bonds", None).unwrap();
+    let plate_appearances = season.get_plate_appearances(&player_id);
+    let indices = plate_appearances
+        .iter()
+        .map(|appearance| *appearance.get_season_index())
+        .collect::<Vec<Option<usize>>>();
+    assert_eq!(
+        indices,
+        (0..plate_appearances.len()).map(Some).collect::<Vec<_>>()
+    );
+
+    // Replaying only the second half of his plate appearances, as for a stint or one pitcher
+    // hand, still finds pitchers as far along as they were
+    let zone_model = ZoneModel::uniform(34.3).with_adaptation(PitcherAdaptation::new(2, 4, 60.0));
+    let half = plate_appearances.len() / 2;
+    for (index, appearance) in plate_appearances[half..].iter().enumerate() {
+        assert_eq!(
+            zone_model.for_plate_appearance(index, appearance),
+            zone_model.for_plate_appearance(half + index, appearance)
+        );
+    }
+    assert!(
+        *zone_model
+            .for_plate_appearance(0, &plate_appearances[half])
+            .get_zone_pct()
+            > 34.3
+    );
+}
@@ -0,0 +1,49 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use no_bat_simulator::{Error, Season};
+
+const FIXTURE: &str = "tests/fixtures";
+
+// A copy of the fixture season with one of Barry Bonds' plate appearances cut short
+fn corrupted_data_dir() -> PathBuf {
+    let data_dir = std::env::temp_dir().join(format!("no_bat_malformed_{}", std::process::id()));
+    let season_dir = data_dir.join("2004eve");
+    fs::create_dir_all(&season_dir).unwrap();
+    for entry in fs::read_dir(Path::new(FIXTURE).join("2004eve")).unwrap() {
+        let path = entry.unwrap().path();
+        fs::copy(&path, season_dir.join(path.file_name().unwrap())).unwrap();
+    }
+    let event_file = season_dir.join("2004SFN.EVN");
+    let contents = fs::read_to_string(&event_file).unwrap().replacen(
+        "play,3,1,bondb001,00,X,S8/G.2-H",
+        "play,3,1,bondb001",
+        1,
+    );
+    fs::write(&event_file, contents).unwrap();
+    data_dir
+}
+
+#[test]
+fn malformed_play_is_skipped_with_a_warning() {
+    let clean = Season::load(Path::new(FIXTURE), 2004).unwrap();
+    assert!(clean.get_warnings().is_empty());
+
+    let data_dir = corrupted_data_dir();
+    let season = Season::load(&data_dir, 2004);
+    fs::remove_dir_all(&data_dir).unwrap();
+    let season = season.unwrap();
+
+    match &season.get_warnings()[..] {
+        [Error::MalformedRecord { file, line, .. }] => {
+            assert!(file.ends_with("2004SFN.EVN"), "{}", file);
+            assert_eq!(*line, 75);
+        }
+        warnings => panic!("expected one malformed record, got {:?}", warnings),
+    }
+    let player_id = season.find_player_id("Barry Bonds", Some("SFN")).unwrap();
+    assert_eq!(
+        season.get_plate_appearances(&player_id).len(),
+        clean.get_plate_appearances(&player_id).len() - 1
+    );
+}
@@ -0,0 +1,63 @@
+use std::path::Path;
+
+use no_bat_simulator::{BattingLine, Season};
+
+// Published season OBPs, rounded to three places as they appear on Retrosheet's player pages
+const OBP_2004: [(&str, &str, f32); 4] = [
+    ("Barry Bonds", "SFN", 0.609),
+    ("Todd Helton", "COL", 0.469),
+    ("Carlos Beltran", "HOU", 0.367),
+    ("Ichiro Suzuki", "SEA", 0.414),
+];
+const OBP_2023: [(&str, &str, f32); 5] = [
+    ("Shohei Ohtani", "ANA", 0.412),
+    ("Juan Soto", "SDN", 0.410),
+    ("Freddie Freeman", "LAN", 0.410),
+    ("Ronald Acuna", "ATL", 0.416),
+    ("Corey Seager", "TEX", 0.390),
+];
+
+fn batting_line(season: &Season, player_name: &str, team_name: &str) -> BattingLine {
+    let player_id = season
+        .find_player_id(player_name, Some(team_name))
+        .unwrap_or_else(|e| panic!("{}", e));
+    BattingLine::from_plate_appearances(season.get_plate_appearances(&player_id))
+}
+
+fn check_published_obp(year: i32, expected: &[(&str, &str, f32)]) {
+    let season = Season::load(Path::new("data"), year).unwrap();
+    for (player_name, team_name, published_obp) in expected {
+        let obp = batting_line(&season, player_name, team_name).on_base_percentage();
+        assert!(
+            (obp - published_obp).abs() < 0.0005,
+            "{} {}: OBP {:.4}, published {:.3}",
+            player_name,
+            year,
+            obp,
+            published_obp
+        );
+    }
+}
+
+#[test]
+fn reproduces_published_obp_2004() {
+    check_published_obp(2004, &OBP_2004);
+}
+
+#[test]
+fn reproduces_published_obp_2023() {
+    check_published_obp(2023, &OBP_2023);
+}
+
+#[test]
+fn bonds_2004_counting_stats() {
+    let season = Season::load(Path::new("data"), 2004).unwrap();
+    let line = batting_line(&season, "Barry Bonds", "SFN");
+    assert_eq!(*line.get_plate_appearances(), 617);
+    assert_eq!(*line.get_at_bats(), 373);
+    assert_eq!(line.hits(), 135);
+    assert_eq!(*line.get_walks(), 232);
+    assert_eq!(*line.get_intentional_walks(), 120);
+    assert_eq!(*line.get_hit_by_pitch(), 9);
+    assert_eq!(*line.get_sacrifice_flies(), 3);
+}
@@ -0,0 +1,99 @@
+use std::path::Path;
+
+use no_bat_simulator::monte_carlo::{replicate_season_no_bat, simulate_season_no_bat};
+use no_bat_simulator::{calculate_obp, PitchCode, PlateAppearance, Season, ZoneModel};
+use rand::SeedableRng;
+use rand_chacha::ChaCha8Rng;
+
+const SEED: u64 = 7;
+
+// Three early-season Giants home games from 2004
+fn fixture_season() -> Season {
+    Season::load(Path::new("tests/fixtures"), 2004).unwrap()
+}
+
+// Barry Bonds' plate appearances replayed once without a bat from `seed`
+fn simulate_bonds(season: &Season, seed: u64) -> Vec<PlateAppearance> {
+    let player_id = season.find_player_id("Barry Bonds", None).unwrap();
+    let (oswing_pct, swing_pct, zone_pct) = season
+        .get_plate_discipline(&player_id)
+        .unwrap()
+        .simulation_rates();
+    let mut rng = ChaCha8Rng::seed_from_u64(seed);
+    simulate_season_no_bat(
+        &mut rng,
+        season.get_plate_appearances(&player_id),
+        oswing_pct,
+        swing_pct,
+        &ZoneModel::uniform(zone_pct),
+    )
+}
+
+// Outcome and pitches of each plate appearance, like `W BBCBB`
+fn outcomes(plate_appearances: &[PlateAppearance]) -> Vec<String> {
+    plate_appearances
+        .iter()
+        .map(|appearance| {
+            let pitches = appearance
+                .get_pitches()
+                .iter()
+                .map(PitchCode::to_char)
+                .collect::<String>();
+            format!("{} {}", appearance.get_outcome(), pitches)
+        })
+        .collect()
+}
+
+#[test]
+fn seeded_replay_is_pinned() {
+    let season = fixture_season();
+    let simulated = simulate_bonds(&season, SEED);
+    assert_eq!(
+        outcomes(&simulated),
+        [
+            "W BBBB",
+            "K BCCC",
+            "K BBBCCC",
+            "K BCCBBC",
+            "W BBBB",
+            "K CCC",
+            "K BCBCC",
+            "K BCCC",
+            "IW ..IIII",
+        ]
+    );
+    assert_eq!(calculate_obp(&simulated), 3.0 / 9.0);
+}
+
+#[test]
+fn same_seed_gives_the_same_replay() {
+    let season = fixture_season();
+    assert_eq!(
+        outcomes(&simulate_bonds(&season, SEED)),
+        outcomes(&simulate_bonds(&season, SEED))
+    );
+
+    let player_id = season.find_player_id("Barry Bonds", None).unwrap();
+    let (oswing_pct, swing_pct, zone_pct) = season
+        .get_plate_discipline(&player_id)
+        .unwrap()
+        .simulation_rates();
+    let replicate = || {
+        let mut rng = ChaCha8Rng::seed_from_u64(SEED);
+        replicate_season_no_bat(
+            &mut rng,
+            season.get_plate_appearances(&player_id),
+            oswing_pct,
+            swing_pct,
+            &ZoneModel::uniform(zone_pct),
+            season.get_run_expectancy(),
+            50,
+        )
+    };
+    let (first, second) = (replicate(), replicate());
+    assert_eq!(first.get_obp().get_values(), second.get_obp().get_values());
+    assert_eq!(
+        first.get_run_values().get_values(),
+        second.get_run_values().get_values()
+    );
+}